@@ -24,6 +24,7 @@ use std::{
     ops,
     path::PathBuf,
     sync::Arc,
+    time::Duration,
 };
 
 pub mod access;
@@ -193,6 +194,12 @@ pub mod __proc_macro_util {
 /// of this trait.
 pub trait AppExtension: 'static {
     /// Register info abound this extension on the info list.
+    ///
+    /// Note that this method can be called more than once before [`init`], the app calls it on the full extension list and on
+    /// each extension to resolve the order constraints declared with [`AppExtensionsInfo::before`] and [`AppExtensionsInfo::after`].
+    /// Implementers must only push info, without side effects.
+    ///
+    /// [`init`]: Self::init
     fn register(&self, info: &mut AppExtensionsInfo)
     where
         Self: Sized,
@@ -241,6 +248,14 @@ pub trait AppExtension: 'static {
         let _ = update;
     }
 
+    /// Called after [`event`](Self::event) only if the event propagation was not stopped.
+    ///
+    /// Extensions can handle this method to implement global fallback behavior, like default
+    /// shortcuts that only apply if no widget or extension handled the event.
+    fn on_unhandled_event(&mut self, update: &mut EventUpdate) {
+        let _ = update;
+    }
+
     /// Called when info rebuild is requested for windows and widgets.
     ///
     /// The [`UiNode::info`] method is called here.
@@ -294,6 +309,16 @@ pub trait AppExtension: 'static {
         let _ = (render_widgets, render_update_widgets);
     }
 
+    /// Called once when the view-process first connects and [`VIEW_PROCESS.is_available`].
+    ///
+    /// Extensions that need the view-process can use this method to register resources. This is called before
+    /// the first [`VIEW_PROCESS_INITED_EVENT`] notifies and it is not called again on respawn, use the event
+    /// for that. It is never called in headless apps without renderer.
+    ///
+    /// [`VIEW_PROCESS.is_available`]: crate::view_process::VIEW_PROCESS::is_available
+    /// [`VIEW_PROCESS_INITED_EVENT`]: crate::view_process::VIEW_PROCESS_INITED_EVENT
+    fn view_process_inited(&mut self) {}
+
     /// Called when the application is exiting.
     ///
     /// Update requests and event notifications generated during this call are ignored,
@@ -309,6 +334,17 @@ pub trait AppExtension: 'static {
     {
         Box::new(self)
     }
+
+    /// Moves the extension to the `list`, composite extensions push each inner extension.
+    ///
+    /// This is used to reorder extensions that declare order constraints.
+    #[doc(hidden)]
+    fn into_list(self, list: &mut Vec<Box<dyn AppExtensionBoxed>>)
+    where
+        Self: Sized,
+    {
+        list.push(self.boxed())
+    }
 }
 
 /// Boxed version of [`AppExtension`].
@@ -323,10 +359,13 @@ pub trait AppExtensionBoxed: 'static {
     fn event_preview_boxed(&mut self, update: &mut EventUpdate);
     fn event_ui_boxed(&mut self, update: &mut EventUpdate);
     fn event_boxed(&mut self, update: &mut EventUpdate);
+    fn on_unhandled_event_boxed(&mut self, update: &mut EventUpdate);
     fn info_boxed(&mut self, info_widgets: &mut InfoUpdates);
     fn layout_boxed(&mut self, layout_widgets: &mut LayoutUpdates);
     fn render_boxed(&mut self, render_widgets: &mut RenderUpdates, render_update_widgets: &mut RenderUpdates);
+    fn view_process_inited_boxed(&mut self);
     fn deinit_boxed(&mut self);
+    fn into_list_boxed(self: Box<Self>, list: &mut Vec<Box<dyn AppExtensionBoxed>>);
 }
 impl<T: AppExtension> AppExtensionBoxed for T {
     fn register_boxed(&self, info: &mut AppExtensionsInfo) {
@@ -369,6 +408,10 @@ impl<T: AppExtension> AppExtensionBoxed for T {
         self.event(update);
     }
 
+    fn on_unhandled_event_boxed(&mut self, update: &mut EventUpdate) {
+        self.on_unhandled_event(update);
+    }
+
     fn layout_boxed(&mut self, layout_widgets: &mut LayoutUpdates) {
         self.layout(layout_widgets);
     }
@@ -377,9 +420,17 @@ impl<T: AppExtension> AppExtensionBoxed for T {
         self.render(render_widgets, render_update_widgets);
     }
 
+    fn view_process_inited_boxed(&mut self) {
+        self.view_process_inited();
+    }
+
     fn deinit_boxed(&mut self) {
         self.deinit();
     }
+
+    fn into_list_boxed(self: Box<Self>, list: &mut Vec<Box<dyn AppExtensionBoxed>>) {
+        (*self).into_list(list);
+    }
 }
 impl AppExtension for Box<dyn AppExtensionBoxed> {
     fn register(&self, info: &mut AppExtensionsInfo) {
@@ -418,6 +469,10 @@ impl AppExtension for Box<dyn AppExtensionBoxed> {
         self.as_mut().event_boxed(update);
     }
 
+    fn on_unhandled_event(&mut self, update: &mut EventUpdate) {
+        self.as_mut().on_unhandled_event_boxed(update);
+    }
+
     fn info(&mut self, info_widgets: &mut InfoUpdates) {
         self.as_mut().info_boxed(info_widgets);
     }
@@ -430,6 +485,10 @@ impl AppExtension for Box<dyn AppExtensionBoxed> {
         self.as_mut().render_boxed(render_widgets, render_update_widgets);
     }
 
+    fn view_process_inited(&mut self) {
+        self.as_mut().view_process_inited_boxed();
+    }
+
     fn deinit(&mut self) {
         self.as_mut().deinit_boxed();
     }
@@ -440,6 +499,13 @@ impl AppExtension for Box<dyn AppExtensionBoxed> {
     {
         self
     }
+
+    fn into_list(self, list: &mut Vec<Box<dyn AppExtensionBoxed>>)
+    where
+        Self: Sized,
+    {
+        self.into_list_boxed(list);
+    }
 }
 
 struct TraceAppExt<E: AppExtension>(E);
@@ -472,6 +538,11 @@ impl<E: AppExtension> AppExtension for TraceAppExt<E> {
         self.0.event(update);
     }
 
+    fn on_unhandled_event(&mut self, update: &mut EventUpdate) {
+        let _span = UpdatesTrace::extension_span::<E>("on_unhandled_event");
+        self.0.on_unhandled_event(update);
+    }
+
     fn update_preview(&mut self) {
         let _span = UpdatesTrace::extension_span::<E>("update_preview");
         self.0.update_preview();
@@ -502,6 +573,11 @@ impl<E: AppExtension> AppExtension for TraceAppExt<E> {
         self.0.render(render_widgets, render_update_widgets);
     }
 
+    fn view_process_inited(&mut self) {
+        let _span = UpdatesTrace::extension_span::<E>("view_process_inited");
+        self.0.view_process_inited();
+    }
+
     fn deinit(&mut self) {
         let _span = UpdatesTrace::extension_span::<E>("deinit");
         self.0.deinit();
@@ -547,9 +623,17 @@ impl AppExtensionInfo {
 }
 
 /// List of app-extensions that are part of an app.
+///
+/// Extensions can also declare order constraints during [`AppExtension::register`], using [`before`] and [`after`]. The
+/// app resolves the constraints on start, moving the extensions into a stable order that satisfies all constraints. Extensions
+/// that have no constraints between them retain the insertion order.
+///
+/// [`before`]: Self::before
+/// [`after`]: Self::after
 #[derive(Clone, PartialEq)]
 pub struct AppExtensionsInfo {
     infos: Vec<AppExtensionInfo>,
+    order: Vec<(AppExtensionInfo, AppExtensionInfo)>,
 }
 impl fmt::Debug for AppExtensionsInfo {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -558,7 +642,10 @@ impl fmt::Debug for AppExtensionsInfo {
 }
 impl AppExtensionsInfo {
     pub(crate) fn start() -> Self {
-        Self { infos: vec![] }
+        Self {
+            infos: vec![],
+            order: vec![],
+        }
     }
 
     /// Push the extension info.
@@ -584,6 +671,105 @@ impl AppExtensionsInfo {
         let info = AppExtensionInfo::new::<E>();
         assert!(self.contains_info(info), "app-extension `{info:?}` is required");
     }
+
+    /// Declare that the last pushed extension must run before the extension `E`.
+    ///
+    /// The constraint is ignored if `E` is not part of the app.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no extension was pushed yet.
+    #[track_caller]
+    pub fn before<E: AppExtension>(&mut self) {
+        let last = *self.infos.last().expect("no app-extension pushed to constrain");
+        self.order.push((last, AppExtensionInfo::new::<E>()));
+    }
+
+    /// Declare that the last pushed extension must run after the extension `E`.
+    ///
+    /// The constraint is ignored if `E` is not part of the app.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no extension was pushed yet.
+    #[track_caller]
+    pub fn after<E: AppExtension>(&mut self) {
+        let last = *self.infos.last().expect("no app-extension pushed to constrain");
+        self.order.push((AppExtensionInfo::new::<E>(), last));
+    }
+
+    /// Order constraints declared by the extensions.
+    ///
+    /// Each entry is a pair of `(first, second)`, the `first` extension must run before the `second`.
+    pub fn order_constraints(&self) -> &[(AppExtensionInfo, AppExtensionInfo)] {
+        &self.order
+    }
+
+    /// Moves the `extensions` to a list in an order that satisfies all order constraints.
+    ///
+    /// Returns `Err(extensions)` if the extensions have no order constraints.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the constraints have a cycle.
+    fn sort<E: AppExtension>(extensions: E) -> Result<Vec<Box<dyn AppExtensionBoxed>>, E> {
+        let mut info = Self::start();
+        extensions.register(&mut info);
+        if info.order.is_empty() {
+            return Err(extensions);
+        }
+
+        let mut list = vec![];
+        extensions.into_list(&mut list);
+
+        // index of the list item that registered each extension
+        let mut owner = HashMap::new();
+        for (i, ext) in list.iter().enumerate() {
+            let mut info = Self::start();
+            ext.register(&mut info);
+            for e in info.infos {
+                owner.insert(e.type_id, i);
+            }
+        }
+
+        // stable topological sort
+        let mut dependencies = vec![0usize; list.len()];
+        let mut edges = vec![];
+        for (first, second) in &info.order {
+            if let (Some(&a), Some(&b)) = (owner.get(&first.type_id), owner.get(&second.type_id)) {
+                if a != b && !edges.contains(&(a, b)) {
+                    edges.push((a, b));
+                    dependencies[b] += 1;
+                }
+            }
+        }
+        let mut sorted = Vec::with_capacity(list.len());
+        let mut done = vec![false; list.len()];
+        while sorted.len() < list.len() {
+            match (0..list.len()).find(|&i| !done[i] && dependencies[i] == 0) {
+                Some(i) => {
+                    done[i] = true;
+                    sorted.push(i);
+                    for &(a, b) in &edges {
+                        if a == i {
+                            dependencies[b] -= 1;
+                        }
+                    }
+                }
+                None => {
+                    let cycle: Vec<_> = info
+                        .infos
+                        .iter()
+                        .filter(|e| owner.get(&e.type_id).map(|&i| !done[i]).unwrap_or(false))
+                        .collect();
+                    panic!("app-extension order constraints have a cycle between {cycle:?}")
+                }
+            }
+        }
+
+        let mut list: Vec<_> = list.into_iter().map(Some).collect();
+        Ok(sorted.into_iter().map(|i| list[i].take().unwrap()).collect())
+    }
 }
 impl ops::Deref for AppExtensionsInfo {
     type Target = [AppExtensionInfo];
@@ -644,6 +830,22 @@ impl HeadlessApp {
         VIEW_PROCESS.is_available()
     }
 
+    /// Panics if headless rendering is enabled.
+    ///
+    /// Tests that assume no renderer, for example tests that run outside of the main thread, can call
+    /// this method to fail early with a clear message.
+    ///
+    /// See [`renderer_enabled`] for more details.
+    ///
+    /// [`renderer_enabled`]: HeadlessApp::renderer_enabled
+    #[track_caller]
+    pub fn assert_renderless(&mut self) {
+        assert!(
+            !self.renderer_enabled(),
+            "expected renderless headless app, but a renderer is enabled, use `run_headless(false)`"
+        );
+    }
+
     /// Does updates unobserved.
     ///
     /// See [`update_observed`] for more details.
@@ -653,6 +855,21 @@ impl HeadlessApp {
         self.update_observed(&mut (), wait_app_event)
     }
 
+    /// Advance the app time by `advance` and does updates, elapsed timers and animations update deterministically.
+    ///
+    /// Starts [manual time] if it is not enabled already, after this call time only advances using this method
+    /// or the other manual time methods, to resume normal time use [`APP.end_manual_time`].
+    ///
+    /// [manual time]: APP::start_manual_time
+    /// [`APP.end_manual_time`]: APP::end_manual_time
+    pub fn advance_time(&mut self, advance: Duration) -> AppControlFlow {
+        if !matches!(INSTANT.mode(), InstantMode::Manual) {
+            APP.start_manual_time();
+        }
+        APP.advance_manual_time(advance);
+        self.update(false)
+    }
+
     /// Does updates observing [`update`] only.
     ///
     /// See [`update_observed`] for more details.
@@ -942,6 +1159,8 @@ impl<'a> AppEventObserver for DynAppEventObserver<'a> {
 
 impl AppExtension for () {
     fn register(&self, _: &mut AppExtensionsInfo) {}
+
+    fn into_list(self, _: &mut Vec<Box<dyn AppExtensionBoxed>>) {}
 }
 impl<A: AppExtension, B: AppExtension> AppExtension for (A, B) {
     fn init(&mut self) {
@@ -1003,13 +1222,32 @@ impl<A: AppExtension, B: AppExtension> AppExtension for (A, B) {
         self.1.event(update);
     }
 
+    fn on_unhandled_event(&mut self, update: &mut EventUpdate) {
+        if !update.args().propagation().is_stopped() {
+            self.0.on_unhandled_event(update);
+        }
+        if !update.args().propagation().is_stopped() {
+            self.1.on_unhandled_event(update);
+        }
+    }
+
+    fn view_process_inited(&mut self) {
+        self.0.view_process_inited();
+        self.1.view_process_inited();
+    }
+
     fn deinit(&mut self) {
         self.1.deinit();
         self.0.deinit();
     }
+
+    fn into_list(self, list: &mut Vec<Box<dyn AppExtensionBoxed>>) {
+        self.0.into_list(list);
+        self.1.into_list(list);
+    }
 }
 
-#[cfg(feature = "dyn_app_extension")]
+// not gated by `dyn_app_extension`, extensions that declare order constraints are sorted into a list in all builds.
 impl AppExtension for Vec<Box<dyn AppExtensionBoxed>> {
     fn init(&mut self) {
         for ext in self {
@@ -1063,6 +1301,15 @@ impl AppExtension for Vec<Box<dyn AppExtensionBoxed>> {
         }
     }
 
+    fn on_unhandled_event(&mut self, update: &mut EventUpdate) {
+        for ext in self {
+            if update.args().propagation().is_stopped() {
+                break;
+            }
+            ext.on_unhandled_event(update);
+        }
+    }
+
     fn info(&mut self, info_widgets: &mut InfoUpdates) {
         for ext in self {
             ext.info(info_widgets);
@@ -1081,11 +1328,23 @@ impl AppExtension for Vec<Box<dyn AppExtensionBoxed>> {
         }
     }
 
+    fn view_process_inited(&mut self) {
+        for ext in self {
+            ext.view_process_inited();
+        }
+    }
+
     fn deinit(&mut self) {
         for ext in self.iter_mut().rev() {
             ext.deinit();
         }
     }
+
+    fn into_list(self, list: &mut Vec<Box<dyn AppExtensionBoxed>>) {
+        for ext in self {
+            ext.into_list(list);
+        }
+    }
 }
 
 /// Start and manage an app process.
@@ -1240,7 +1499,7 @@ impl AppExtended<Vec<Box<dyn AppExtensionBoxed>>> {
     fn run_dyn(self, start: std::pin::Pin<Box<dyn Future<Output = ()> + Send + 'static>>) {
         let app = RunningApp::start(
             self._cleanup,
-            self.extensions,
+            AppExtensionsInfo::sort(self.extensions).unwrap_or_else(|e| e),
             true,
             true,
             self.view_process_exe,
@@ -1255,7 +1514,7 @@ impl AppExtended<Vec<Box<dyn AppExtensionBoxed>>> {
     fn run_headless_dyn(self, with_renderer: bool) -> HeadlessApp {
         let app = RunningApp::start(
             self._cleanup,
-            self.extensions.boxed(),
+            AppExtensionsInfo::sort(self.extensions).unwrap_or_else(|e| e).boxed(),
             false,
             with_renderer,
             self.view_process_exe,
@@ -1324,7 +1583,7 @@ impl<E: AppExtension> AppExtended<E> {
     fn run_impl(self, start: impl Future<Output = ()> + Send + 'static) {
         let app = RunningApp::start(
             self._cleanup,
-            self.extensions,
+            Self::sort_boxed(self.extensions),
             true,
             true,
             self.view_process_exe,
@@ -1339,7 +1598,7 @@ impl<E: AppExtension> AppExtended<E> {
     fn run_headless_impl(self, with_renderer: bool) -> HeadlessApp {
         let app = RunningApp::start(
             self._cleanup,
-            self.extensions.boxed(),
+            Self::sort_boxed(self.extensions),
             false,
             with_renderer,
             self.view_process_exe,
@@ -1348,6 +1607,13 @@ impl<E: AppExtension> AppExtended<E> {
 
         HeadlessApp { app }
     }
+
+    /// Sort the extensions and box, so that the app loop is only instantiated for one extension type.
+    fn sort_boxed(extensions: E) -> Box<dyn AppExtensionBoxed> {
+        AppExtensionsInfo::sort(extensions)
+            .map(AppExtension::boxed)
+            .unwrap_or_else(AppExtension::boxed)
+    }
 }
 impl<E: AppExtension> AppExtended<E> {
     /// Set the path to the executable for the *View Process*.
@@ -1386,6 +1652,10 @@ impl<E: AppExtension> AppExtended<E> {
     ///
     /// If `with_renderer` is `true` spawns a renderer process for headless rendering. See [`HeadlessApp::renderer_enabled`]
     /// for more details.
+    ///
+    /// Note that the renderer requires the view-process, that must be initialized in the main thread, so tests that run
+    /// in the test harness worker threads must use `run_headless(false)`. You can call [`HeadlessApp::assert_renderless`]
+    /// to guard against a renderer being enabled.
     pub fn run_headless(self, with_renderer: bool) -> HeadlessApp {
         self.run_headless_impl(with_renderer)
     }
@@ -23,6 +23,7 @@ use zng_view_api::{
     config::FontAntiAliasing,
     display_list::{DisplayList, DisplayListBuilder, FilterOp, NinePatchSource, ReuseStart},
     font::{GlyphInstance, GlyphOptions},
+    image::ExternalImageId,
     window::FrameId,
     ReferenceFrameId as RenderReferenceFrameId, ViewProcessGen,
 };
@@ -147,9 +148,22 @@ pub struct FrameBuilder {
     widget_count: usize,
     widget_count_offsets: ParallelSegmentOffsets,
 
+    display_item_budget: Option<usize>,
+    display_item_budget_warned: bool,
+
+    reuse_budget: Option<u32>,
+    reuse_evicted_count: usize,
+
     debug_dot_overlays: Vec<(PxPoint, Rgba)>,
 }
 impl FrameBuilder {
+    /// Default display item soft budget in debug builds.
+    ///
+    /// See [`display_item_budget`] for more details.
+    ///
+    /// [`display_item_budget`]: Self::display_item_budget
+    pub const DEFAULT_DISPLAY_ITEM_BUDGET: usize = 50_000;
+
     /// New builder.
     ///
     /// * `render_widgets` - External render requests.
@@ -225,6 +239,16 @@ impl FrameBuilder {
             widget_count: 0,
             widget_count_offsets: ParallelSegmentOffsets::default(),
 
+            display_item_budget: if cfg!(debug_assertions) {
+                Some(Self::DEFAULT_DISPLAY_ITEM_BUDGET)
+            } else {
+                None
+            },
+            display_item_budget_warned: false,
+
+            reuse_budget: None,
+            reuse_evicted_count: 0,
+
             clear_color: Some(colors::BLACK.transparent()),
 
             debug_dot_overlays: vec![],
@@ -327,6 +351,95 @@ impl FrameBuilder {
         self.auto_hit_test
     }
 
+    /// Number of display items generated so far.
+    ///
+    /// Note that in a [`parallel_split`] builder this only counts the items generated in the split.
+    ///
+    /// [`parallel_split`]: Self::parallel_split
+    pub fn display_item_count(&self) -> usize {
+        self.display_list.len()
+    }
+
+    /// Display items soft budget.
+    ///
+    /// If the [`display_item_count`] exceeds this value a warning is logged once for the frame, with the path
+    /// of the widget that was rendering when the budget was exceeded. This is a development aid to catch runaway
+    /// display lists, the frame is still fully rendered.
+    ///
+    /// Is [`DEFAULT_DISPLAY_ITEM_BUDGET`] in debug builds and `None` in release builds by default. Note that the builder
+    /// is created for each frame, the window implementation sets the budget from a persistent window config, the
+    /// `WindowVars::display_item_budget` variable in the default implementation.
+    ///
+    /// [`display_item_count`]: Self::display_item_count
+    /// [`DEFAULT_DISPLAY_ITEM_BUDGET`]: Self::DEFAULT_DISPLAY_ITEM_BUDGET
+    pub fn display_item_budget(&self) -> Option<usize> {
+        self.display_item_budget
+    }
+
+    /// Set the display items soft budget for the rest of the frame.
+    ///
+    /// Widgets can use this to change the budget for a single frame. See [`display_item_budget`] for more details.
+    ///
+    /// [`display_item_budget`]: Self::display_item_budget
+    pub fn set_display_item_budget(&mut self, budget: Option<usize>) {
+        self.display_item_budget = budget;
+    }
+
+    /// Display list reuse budget, in frames.
+    ///
+    /// Reused display items are retained by the view-process in the full display list of the frame that generated them, so
+    /// a widget that is reused forever can keep an old frame alive. If set, reuse ranges generated more than this number of
+    /// full frames ago are dropped and regenerated, the oldest ranges are the least recently generated, so this caps the
+    /// number of previous frames the view-process must retain for reuse.
+    ///
+    /// Is `None` by default, no limit. Note that the builder is created for each frame, the window implementation sets the
+    /// budget from a persistent window config, the `WindowVars::reuse_budget` variable in the default implementation.
+    pub fn reuse_budget(&self) -> Option<u32> {
+        self.reuse_budget
+    }
+
+    /// Set the display list reuse budget for the rest of the frame.
+    ///
+    /// Widgets can use this to change the budget for a single frame. See [`reuse_budget`] for more details.
+    ///
+    /// [`reuse_budget`]: Self::reuse_budget
+    pub fn set_reuse_budget(&mut self, max_frame_age: Option<u32>) {
+        self.reuse_budget = max_frame_age;
+    }
+
+    /// Number of reuse ranges that were regenerated because they exceeded the [`reuse_budget`].
+    ///
+    /// Note that in a [`parallel_split`] builder this only counts the ranges evicted in the split.
+    ///
+    /// [`reuse_budget`]: Self::reuse_budget
+    /// [`parallel_split`]: Self::parallel_split
+    pub fn reuse_evicted_count(&self) -> usize {
+        self.reuse_evicted_count
+    }
+
+    fn reuse_budget_exceeded(&self, range: &ReuseRange) -> bool {
+        if let Some(max_age) = self.reuse_budget {
+            let age = self.frame_id.epoch().wrapping_sub(range.frame_id().epoch());
+            age > max_age
+        } else {
+            false
+        }
+    }
+
+    fn check_display_item_budget(&mut self) {
+        if let Some(budget) = self.display_item_budget {
+            if !self.display_item_budget_warned && self.display_list.len() > budget {
+                self.display_item_budget_warned = true;
+                tracing::warn!(
+                    "frame {:?} exceeded the display item budget ({} > {budget}) while rendering `{}`",
+                    self.frame_id,
+                    self.display_list.len(),
+                    WIDGET.trace_path(),
+                );
+            }
+        }
+    }
+
     /// Runs `render` with `aa` used as the default text anti-aliasing mode.
     pub fn with_default_font_aa(&mut self, aa: FontAntiAliasing, render: impl FnOnce(&mut Self)) {
         let parent = mem::replace(&mut self.default_font_aa, aa);
@@ -376,13 +489,16 @@ impl FrameBuilder {
     /// are discouraged.
     ///
     /// If the widget has been rendered before, render was not requested for it and [`can_reuse`] allows reuse, the `render`
-    /// closure is not called, an only a reference to the widget range in the previous frame is send.
+    /// closure is not called, an only a reference to the widget range in the previous frame is send. To force a widget
+    /// to render again request a render for it using [`UPDATES.render`] or [`WIDGET.render`].
     ///
     /// If the widget is collapsed during layout it is not rendered. See [`WidgetLayout::collapse`] for more details.
     ///
     /// [`is_outer`]: Self::is_outer
     /// [`push_inner`]: Self::push_inner
     /// [`can_reuse`]: Self::can_reuse
+    /// [`UPDATES.render`]: crate::update::UPDATES::render
+    /// [`WIDGET.render`]: crate::widget::WIDGET::render
     /// [`WidgetLayout::collapse`]: crate::widget::info::WidgetLayout::collapse
     pub fn push_widget(&mut self, render: impl FnOnce(&mut Self)) {
         let wgt_info = WIDGET.info();
@@ -642,6 +758,8 @@ impl FrameBuilder {
                 }),
                 tree,
             );
+
+            self.check_display_item_budget();
         }
 
         self.visible = parent_visible;
@@ -676,15 +794,22 @@ impl FrameBuilder {
     /// even when the widget itself is not reused, an example is a widget that renders text and a background, the entire widget is invalidated when the
     /// background changes, but the text is the same, so placing the text in a reuse group avoids having to upload all glyphs again.
     ///
+    /// If `group` exceeds the [`reuse_budget`] it is regenerated.
+    ///
     /// [`can_reuse`]: Self::can_reuse
     /// [`push_widget`]: Self::push_widget
+    /// [`reuse_budget`]: Self::reuse_budget
     pub fn push_reuse(&mut self, group: &mut Option<ReuseRange>, generate: impl FnOnce(&mut Self)) {
         if self.can_reuse {
             if let Some(g) = &group {
-                if self.visible {
-                    self.display_list.push_reuse_range(g);
+                if !self.reuse_budget_exceeded(g) {
+                    if self.visible {
+                        self.display_list.push_reuse_range(g);
+                    }
+                    return;
                 }
-                return;
+                self.reuse_evicted_count += 1;
+                tracing::trace!("reuse range from frame {:?} exceeded the reuse budget, regenerating", g.frame_id());
             }
         }
         *group = None;
@@ -767,6 +892,8 @@ impl FrameBuilder {
     ///
     /// When [`push_inner`] is called a stacking context is created for the widget that includes the opacity filter.
     ///
+    /// The opacity can be bound and updated using [`FrameUpdate::update_f32`].
+    ///
     /// [`is_outer`]: Self::is_outer
     /// [`push_inner`]: Self::push_inner
     pub fn push_inner_opacity(&mut self, bind: FrameValue<f32>, render: impl FnOnce(&mut Self)) {
@@ -1060,6 +1187,21 @@ impl FrameBuilder {
         }
     }
 
+    /// Push a hit-test `area` identified by the `tag`.
+    ///
+    /// The `tag` is returned in [`HitInfo::tag`] when the area is the top-most hit-test shape of the widget at the hit point,
+    /// nodes that render many hoverable regions can use this to identify the region without declaring a widget for each.
+    ///
+    /// This is a shorthand for [`HitTestBuilder::push_tag`] in the [`hit_test`] builder.
+    ///
+    /// [`HitInfo::tag`]: crate::widget::info::HitInfo::tag
+    /// [`hit_test`]: Self::hit_test
+    pub fn push_hit_test_tag(&mut self, tag: u64, area: PxRect) {
+        expect_inner!(self.push_hit_test_tag);
+
+        self.hit_test().push_tag(tag, area);
+    }
+
     /// Calls `render` with a new clip context that adds the `clip_rect`.
     ///
     /// If `clip_out` is `true` only pixels outside the rect are visible. If `hit_test` is `true` the hit-test shapes
@@ -1185,6 +1327,35 @@ impl FrameBuilder {
         }
     }
 
+    /// Calls `render` inside a new sticky spatial frame.
+    ///
+    /// The content rendered inside scrolls normally until it reaches the viewport edge `margins`, then it pins to
+    /// the edge. The `frame_rect` is the sticky content bounds, `vertical_range` and `horizontal_range` are the
+    /// minimum and maximum offset the content can be moved by the renderer to stay pinned, `None` margins do not pin.
+    ///
+    /// Note that the sticky offset is computed by the renderer, it is not included in the hit-test transform
+    /// or in the widget bounds info.
+    pub fn push_sticky_frame(
+        &mut self,
+        key: ReferenceFrameId,
+        frame_rect: PxRect,
+        margins: euclid::SideOffsets2D<Option<Px>, Px>,
+        vertical_range: (Px, Px),
+        horizontal_range: (Px, Px),
+        render: impl FnOnce(&mut Self),
+    ) {
+        if self.visible {
+            self.display_list
+                .push_sticky_frame(key.into(), frame_rect, margins, vertical_range, horizontal_range);
+
+            render(self);
+
+            self.display_list.pop_sticky_frame();
+        } else {
+            render(self);
+        }
+    }
+
     /// Calls `render` with added `blend` and `filter` stacking context.
     ///
     /// Note that this introduces a new stacking context, you can use the [`push_inner_blend`] and [`push_inner_filter`] methods to
@@ -1208,6 +1379,8 @@ impl FrameBuilder {
 
     /// Calls `render` with added opacity stacking context.
     ///
+    /// The opacity can be bound and updated using [`FrameUpdate::update_f32`].
+    ///
     /// Note that this introduces a new stacking context, you can use the [`push_inner_opacity`] method to
     /// add to the widget stacking context.
     ///
@@ -1456,6 +1629,26 @@ impl FrameBuilder {
         }
     }
 
+    /// Calls `render` with a text shadow context, all text runs pushed inside `render` also render a shadow.
+    ///
+    /// The shadow is rendered by the GPU from the same glyphs, this is less costly then rendering the text twice
+    /// with a blur filter.
+    ///
+    /// Note that nested text shadow contexts are not supported, the inner context also ends the parent shadows.
+    pub fn push_text_shadow(&mut self, offset: PxVector, blur_radius: Px, color: Rgba, render: impl FnOnce(&mut Self)) {
+        expect_inner!(self.push_text_shadow);
+
+        if self.visible {
+            self.display_list.push_shadow(offset.cast(), color, blur_radius.0 as f32);
+
+            render(self);
+
+            self.display_list.pop_all_shadows();
+        } else {
+            render(self);
+        }
+    }
+
     /// Push an image.
     pub fn push_image(
         &mut self,
@@ -1489,6 +1682,26 @@ impl FrameBuilder {
         }
     }
 
+    /// Push an external image or texture.
+    ///
+    /// The `key` identifies an image or texture registered in the renderer by a view-process extension, the
+    /// image is stretched to fill the `rect` and composited with the current transform and clip.
+    ///
+    /// In the default view-process the `key` is the ID returned by `ExternalImages::register_texture` or
+    /// `ExternalImages::register_image`, usually send to the app-process by the extension after the renderer inits.
+    pub fn push_external_image(&mut self, key: ExternalImageId, rect: PxRect) {
+        expect_inner!(self.push_external_image);
+        warn_empty!(self.push_external_image(rect));
+
+        if self.renderer.is_some() && self.visible {
+            self.display_list.push_external_image(rect, key);
+        }
+
+        if self.auto_hit_test {
+            self.hit_test().push_rect(rect);
+        }
+    }
+
     /// Push a color rectangle.
     ///
     /// The `color` can be bound and updated using [`FrameUpdate::update_color`], note that if the color binding or update
@@ -1510,6 +1723,35 @@ impl FrameBuilder {
         }
     }
 
+    /// Push a color rectangle with rounded corners.
+    ///
+    /// Each corner of `radii` can be different, the rectangle is rendered as a single color item clipped by a rounded clip,
+    /// without the extra items generated by [`push_border`] or [`push_clip_rounded_rect`].
+    ///
+    /// The `color` can be bound and updated using [`FrameUpdate::update_color`], see [`push_color`] for more details.
+    ///
+    /// [`push_border`]: Self::push_border
+    /// [`push_clip_rounded_rect`]: Self::push_clip_rounded_rect
+    /// [`push_color`]: Self::push_color
+    pub fn push_rounded_rect(&mut self, rect: PxRect, radii: PxCornerRadius, color: FrameValue<Rgba>) {
+        expect_inner!(self.push_rounded_rect);
+        warn_empty!(self.push_rounded_rect(rect));
+
+        if self.visible {
+            if radii == PxCornerRadius::zero() {
+                self.display_list.push_color(rect, color);
+            } else {
+                self.display_list.push_clip_rounded_rect(rect, radii, false);
+                self.display_list.push_color(rect, color);
+                self.display_list.pop_clip();
+            }
+        }
+
+        if self.auto_hit_test {
+            self.hit_test().push_rounded_rect(rect, radii);
+        }
+    }
+
     /// Push a repeating linear gradient rectangle.
     ///
     /// The gradient fills the `tile_size`, the tile is repeated to fill the `rect`.
@@ -1828,6 +2070,10 @@ impl FrameBuilder {
             clear_color: None,
             widget_count: 0,
             widget_count_offsets: self.widget_count_offsets.parallel_split(),
+            display_item_budget: self.display_item_budget,
+            display_item_budget_warned: self.display_item_budget_warned,
+            reuse_budget: self.reuse_budget,
+            reuse_evicted_count: 0,
             debug_dot_overlays: vec![],
         }))
     }
@@ -1845,6 +2091,11 @@ impl FrameBuilder {
 
         self.widget_count += split.widget_count;
         self.debug_dot_overlays.extend(split.debug_dot_overlays);
+
+        self.display_item_budget_warned |= split.display_item_budget_warned;
+        self.check_display_item_budget();
+
+        self.reuse_evicted_count += split.reuse_evicted_count;
     }
 
     /// Finalizes the build.
@@ -1999,6 +2250,18 @@ impl<'a> HitTestBuilder<'a> {
         }
     }
 
+    /// Push a hit-test `area` that identifies sub-regions of the widget.
+    ///
+    /// The `tag` is returned in [`HitInfo::tag`] when this is the top-most shape hit in the widget, this enables
+    /// nodes that render many hoverable regions to identify the region without declaring a widget for each.
+    ///
+    /// [`HitInfo::tag`]: crate::widget::info::HitInfo::tag
+    pub fn push_tag(&mut self, tag: u64, area: PxRect) {
+        if self.is_hit_testable && area.size != PxSize::zero() {
+            self.hit_clips.push_tag(tag, area.to_box2d());
+        }
+    }
+
     /// Push a clip `rect` that affects the `inner_hit_test`.
     pub fn push_clip_rect(&mut self, rect: PxRect, clip_out: bool, inner_hit_test: impl FnOnce(&mut Self)) {
         if !self.is_hit_testable {
@@ -2532,6 +2795,10 @@ impl FrameUpdate {
     }
 
     /// Update a float value.
+    ///
+    /// This is used to update opacity values bound in [`FrameBuilder::push_opacity`] or [`FrameBuilder::push_inner_opacity`]
+    /// filters, if the binding or update is flagged as `animating` webrender frame updates are used, so fade animations
+    /// do not need a new frame.
     pub fn update_f32(&mut self, new_value: FrameValueUpdate<f32>) {
         if self.visible {
             self.floats.push(new_value);
@@ -2978,3 +3245,79 @@ impl_from_and_into_var! {
         if enabled { FontSynthesis::ENABLED } else { FontSynthesis::DISABLED }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::window::WindowId;
+
+    fn test_frame(frame_id: FrameId, tree: &WidgetInfoTree, reuse_budget: Option<u32>) -> FrameBuilder {
+        let mut frame = FrameBuilder::new(
+            Arc::default(),
+            Arc::default(),
+            frame_id,
+            tree.root().id(),
+            &WidgetBoundsInfo::new(),
+            tree,
+            None,
+            1.fct(),
+            FontAntiAliasing::Default,
+        );
+        // simulate a view-process that has the previous frames
+        frame.can_reuse = true;
+        frame.set_reuse_budget(reuse_budget);
+        frame
+    }
+
+    #[test]
+    fn reuse_budget_regenerates_old_ranges() {
+        let tree = WidgetInfoTree::wgt(WindowId::new_unique(), WidgetId::new_unique());
+
+        let mut frame_id = FrameId::first();
+        let mut group = None;
+        let mut generated = 0;
+
+        let mut frame = test_frame(frame_id, &tree, Some(2));
+        frame.push_reuse(&mut group, |_| generated += 1);
+        assert_eq!(1, generated);
+        assert_eq!(frame_id, group.as_ref().unwrap().frame_id());
+
+        for _ in 0..2 {
+            frame_id = frame_id.next();
+            let mut frame = test_frame(frame_id, &tree, Some(2));
+            frame.push_reuse(&mut group, |_| generated += 1);
+            assert_eq!(1, generated, "expected reuse inside budget");
+            assert_eq!(0, frame.reuse_evicted_count());
+        }
+
+        frame_id = frame_id.next();
+        let mut frame = test_frame(frame_id, &tree, Some(2));
+        frame.push_reuse(&mut group, |_| generated += 1);
+        assert_eq!(2, generated, "expected regenerate after budget exceeded");
+        assert_eq!(1, frame.reuse_evicted_count());
+        assert_eq!(frame_id, group.as_ref().unwrap().frame_id());
+
+        frame_id = frame_id.next();
+        let mut frame = test_frame(frame_id, &tree, Some(2));
+        frame.push_reuse(&mut group, |_| generated += 1);
+        assert_eq!(2, generated, "expected reuse of the regenerated range");
+        assert_eq!(0, frame.reuse_evicted_count());
+    }
+
+    #[test]
+    fn reuse_budget_none_always_reuses() {
+        let tree = WidgetInfoTree::wgt(WindowId::new_unique(), WidgetId::new_unique());
+
+        let mut frame_id = FrameId::first();
+        let mut group = None;
+        let mut generated = 0;
+
+        for _ in 0..10 {
+            let mut frame = test_frame(frame_id, &tree, None);
+            frame.push_reuse(&mut group, |_| generated += 1);
+            assert_eq!(0, frame.reuse_evicted_count());
+            frame_id = frame_id.next();
+        }
+        assert_eq!(1, generated);
+    }
+}
@@ -46,6 +46,7 @@ pub(crate) struct RunningApp<E: AppExtension> {
     pending_view_frame_events: Vec<zng_view_api::window::EventFrameRendered>,
     pending: ContextUpdates,
 
+    view_process_inited: bool,
     exited: bool,
 
     // cleans on drop
@@ -125,6 +126,7 @@ impl<E: AppExtension> RunningApp<E> {
                 render_widgets: RenderUpdates::default(),
                 render_update_widgets: RenderUpdates::default(),
             },
+            view_process_inited: false,
             exited: false,
 
             _scope: scope,
@@ -153,6 +155,10 @@ impl<E: AppExtension> RunningApp<E> {
         self.extensions.event(&mut update);
         observer.event(&mut update);
         update.call_pos_actions();
+
+        if !update.args().propagation().is_stopped() {
+            self.extensions.on_unhandled_event(&mut update);
+        }
     }
 
     fn device_id(&mut self, id: zng_view_api::DeviceId) -> DeviceId {
@@ -206,6 +212,10 @@ impl<E: AppExtension> RunningApp<E> {
                 );
                 self.notify_event(RAW_WINDOW_CHANGED_EVENT.new_update(args), observer);
             }
+            Event::FramePresented { window: w_id, frame } => {
+                let args = RawFramePresentedArgs::now(window_id(w_id), frame);
+                self.notify_event(RAW_FRAME_PRESENTED_EVENT.new_update(args), observer);
+            }
             Event::DroppedFile { window: w_id, file } => {
                 let args = RawDroppedFileArgs::now(window_id(w_id), file);
                 self.notify_event(RAW_DROPPED_FILE_EVENT.new_update(args), observer);
@@ -308,6 +318,10 @@ impl<E: AppExtension> RunningApp<E> {
                 let args = RawMonitorsChangedArgs::now(monitors);
                 self.notify_event(RAW_MONITORS_CHANGED_EVENT.new_update(args), observer);
             }
+            Event::WindowVideoModeChanged { window: w_id, mode } => {
+                let args = RawWindowVideoModeChangedArgs::now(window_id(w_id), mode);
+                self.notify_event(RAW_WINDOW_VIDEO_MODE_CHANGED_EVENT.new_update(args), observer);
+            }
             Event::WindowCloseRequested(w_id) => {
                 let args = RawWindowCloseRequestedArgs::now(window_id(w_id));
                 self.notify_event(RAW_WINDOW_CLOSE_REQUESTED_EVENT.new_update(args), observer);
@@ -369,9 +383,19 @@ impl<E: AppExtension> RunningApp<E> {
                     self.notify_event(RAW_IMAGE_LOAD_ERROR_EVENT.new_update(args), observer);
                 }
             }
-            Event::ImageEncoded { image: id, format, data } => VIEW_PROCESS.on_image_encoded(id, format, data),
-            Event::ImageEncodeError { image: id, format, error } => {
-                VIEW_PROCESS.on_image_encode_error(id, format, error);
+            Event::ImageEncoded {
+                image: id,
+                request,
+                format,
+                data,
+            } => VIEW_PROCESS.on_image_encoded(id, request, format, data),
+            Event::ImageEncodeError {
+                image: id,
+                request,
+                format,
+                error,
+            } => {
+                VIEW_PROCESS.on_image_encode_error(id, request, format, error);
             }
             Event::FrameImageReady {
                 window: w_id,
@@ -490,6 +514,11 @@ impl<E: AppExtension> RunningApp<E> {
 
             Event::LowMemory => {}
 
+            Event::MemoryPressure { level } => {
+                let args = MemoryPressureArgs::now(level);
+                self.notify_event(MEMORY_PRESSURE_EVENT.new_update(args), observer);
+            }
+
             Event::RecoveredFromComponentPanic { component, recover, panic } => {
                 tracing::error!("view-process recovered from internal component panic\n  component: {component}\n  recover: {recover}\n```panic\n{panic}\n```");
             }
@@ -572,6 +601,12 @@ impl<E: AppExtension> RunningApp<E> {
 
                     VIEW_PROCESS.handle_inited(generation, extensions.clone());
 
+                    if !self.view_process_inited {
+                        self.view_process_inited = true;
+                        let _s = tracing::debug_span!("extensions.view_process_inited").entered();
+                        self.extensions.view_process_inited();
+                    }
+
                     let monitors: Vec<_> = available_monitors
                         .into_iter()
                         .map(|(id, info)| (VIEW_PROCESS.monitor_id(id), info))
@@ -857,6 +892,11 @@ impl<E: AppExtension> RunningApp<E> {
                         observer.event(&mut update);
                     }
                     update.call_pos_actions();
+
+                    if !update.args().propagation().is_stopped() {
+                        let _s = tracing::debug_span!("ext.on_unhandled_event").entered();
+                        self.extensions.on_unhandled_event(&mut update);
+                    }
                 });
 
                 self.apply_updates(observer);
@@ -1347,9 +1387,7 @@ impl AppProcessService {
     }
 
     pub(super) fn extensions(&self) -> Arc<AppExtensionsInfo> {
-        self.extensions
-            .clone()
-            .unwrap_or_else(|| Arc::new(AppExtensionsInfo { infos: vec![] }))
+        self.extensions.clone().unwrap_or_else(|| Arc::new(AppExtensionsInfo::start()))
     }
 
     pub(super) fn set_extensions(&mut self, info: AppExtensionsInfo, device_events: bool) {
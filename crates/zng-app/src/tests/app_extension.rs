@@ -0,0 +1,122 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use crate::{
+    event::{event, event_args, AnyEventArgs as _},
+    update::EventUpdate,
+    AppExtension, AppExtensionInfo, AppExtensionsInfo, APP,
+};
+
+struct Foo;
+impl AppExtension for Foo {
+    fn register(&self, info: &mut AppExtensionsInfo) {
+        info.push::<Self>();
+        info.after::<Bar>();
+    }
+}
+
+struct Bar;
+impl AppExtension for Bar {}
+
+struct Baz;
+impl AppExtension for Baz {
+    fn register(&self, info: &mut AppExtensionsInfo) {
+        info.push::<Self>();
+        info.before::<Foo>();
+    }
+}
+
+fn position<E: AppExtension>() -> usize {
+    let info = AppExtensionInfo::new::<E>();
+    APP.extensions().iter().position(|e| *e == info).unwrap()
+}
+
+#[test]
+fn order_constraints() {
+    let _app = APP.minimal().extend(Foo).extend(Baz).extend(Bar).run_headless(false);
+
+    assert!(position::<Bar>() < position::<Foo>());
+    assert!(position::<Baz>() < position::<Foo>());
+    // no constraint between `Baz` and `Bar`, retains insertion order.
+    assert!(position::<Baz>() < position::<Bar>());
+    assert_eq!(2, APP.extensions().order_constraints().len());
+}
+
+#[test]
+fn order_constraints_unchanged() {
+    let _app = APP.minimal().extend(Bar).extend(Foo).run_headless(false);
+
+    assert_eq!(position::<Bar>() + 1, position::<Foo>());
+}
+
+struct Cycle;
+impl AppExtension for Cycle {
+    fn register(&self, info: &mut AppExtensionsInfo) {
+        info.push::<Self>();
+        info.before::<Bar>();
+    }
+}
+
+#[test]
+#[should_panic(expected = "cycle")]
+fn order_constraints_cycle() {
+    struct Cycle2;
+    impl AppExtension for Cycle2 {
+        fn register(&self, info: &mut AppExtensionsInfo) {
+            info.push::<Self>();
+            info.before::<Cycle>();
+            info.after::<Bar>();
+        }
+    }
+    let _app = APP.minimal().extend(Cycle).extend(Bar).extend(Cycle2).run_headless(false);
+}
+
+event_args! {
+    struct UnhandledTestArgs {
+        handle: bool,
+
+        ..
+
+        fn delivery_list(&self, _list: &mut UpdateDeliveryList) {}
+    }
+}
+
+event! {
+    static UNHANDLED_TEST_EVENT: UnhandledTestArgs;
+}
+
+struct Handler;
+impl AppExtension for Handler {
+    fn event(&mut self, update: &mut EventUpdate) {
+        if let Some(args) = UNHANDLED_TEST_EVENT.on(update) {
+            if args.handle {
+                args.propagation().stop();
+            }
+        }
+    }
+}
+
+struct Fallback(Arc<AtomicUsize>);
+impl AppExtension for Fallback {
+    fn on_unhandled_event(&mut self, update: &mut EventUpdate) {
+        if UNHANDLED_TEST_EVENT.on(update).is_some() {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+#[test]
+fn on_unhandled_event() {
+    let count = Arc::new(AtomicUsize::new(0));
+    let mut app = APP.minimal().extend(Handler).extend(Fallback(count.clone())).run_headless(false);
+
+    UNHANDLED_TEST_EVENT.notify(UnhandledTestArgs::now(false));
+    app.update(false).assert_wait();
+    assert_eq!(1, count.load(Ordering::Relaxed));
+
+    UNHANDLED_TEST_EVENT.notify(UnhandledTestArgs::now(true));
+    app.update(false).assert_wait();
+    assert_eq!(1, count.load(Ordering::Relaxed));
+}
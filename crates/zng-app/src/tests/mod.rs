@@ -4,7 +4,9 @@ mod ui_node;
 mod widget;
 
 mod a;
+mod app_extension;
 mod b;
+mod timer;
 mod ui_node_list;
 
 pub use a::Foo as FooA;
@@ -0,0 +1,30 @@
+use crate::{layout::unit::TimeUnits as _, timer::TIMERS, var::Var as _, APP};
+
+#[test]
+fn advance_time_elapses_deadline() {
+    let mut app = APP.minimal().run_headless(false);
+
+    let deadline = TIMERS.deadline(1.hours());
+    app.update(false).assert_wait();
+    assert!(!deadline.get().has_elapsed());
+
+    app.advance_time(30.minutes()).assert_wait();
+    assert!(!deadline.get().has_elapsed());
+
+    app.advance_time(30.minutes()).assert_wait();
+    assert!(deadline.get().has_elapsed());
+}
+
+#[test]
+fn advance_time_interval_ticks() {
+    let mut app = APP.minimal().run_headless(false);
+
+    let timer = TIMERS.interval(1.secs(), false);
+    app.update(false).assert_wait();
+    assert_eq!(0, timer.get().count());
+
+    for i in 1..=5 {
+        app.advance_time(1.secs()).assert_wait();
+        assert_eq!(i, timer.get().count());
+    }
+}
@@ -725,9 +725,21 @@ impl UpdatesTrace {
     }
 
     /// Opens an app extension span.
+    ///
+    /// The span is only created if the updates trace is collecting and the span level is enabled, it
+    /// records the [`APP.id`] sequential number as the `app_id` field.
+    ///
+    /// [`APP.id`]: crate::APP::id
     pub fn extension_span<E: AppExtension>(ext_mtd: &'static str) -> tracing::span::EnteredSpan {
-        if Self::is_tracing() {
-            tracing::trace_span!(target: UpdatesTrace::UPDATES_TARGET, "AppExtension", name = pretty_type_name::pretty_type_name::<E>(), %ext_mtd).entered()
+        if Self::is_tracing() && tracing::enabled!(target: UpdatesTrace::UPDATES_TARGET, tracing::Level::TRACE) {
+            tracing::trace_span!(
+                target: UpdatesTrace::UPDATES_TARGET,
+                "AppExtension",
+                name = pretty_type_name::pretty_type_name::<E>(),
+                %ext_mtd,
+                app_id = crate::APP.id().map(|id| id.sequential())
+            )
+            .entered()
         } else {
             tracing::span::Span::none().entered()
         }
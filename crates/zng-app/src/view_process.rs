@@ -5,6 +5,7 @@ use std::{
     fmt,
     path::PathBuf,
     sync::{self, Arc},
+    time::Duration,
 };
 
 pub mod raw_device_events;
@@ -24,23 +25,23 @@ use zng_var::ResponderVar;
 use zng_view_api::{
     self,
     api_extension::{ApiExtensionId, ApiExtensionName, ApiExtensionPayload, ApiExtensionRecvError, ApiExtensions},
-    config::{AnimationsConfig, ColorsConfig, FontAntiAliasing, LocaleConfig, MultiClickConfig, TouchConfig},
+    config::{AnimationsConfig, ColorScheme, ColorsConfig, FontAntiAliasing, LocaleConfig, MultiClickConfig, TouchConfig},
     dialog::{FileDialog, FileDialogResponse, MsgDialog, MsgDialogResponse},
     font::FontOptions,
     image::{ImageMaskMode, ImagePpi, ImageRequest, ImageTextureId},
     ipc::{IpcBytes, IpcBytesReceiver},
     window::{
-        CursorIcon, FocusIndicator, FrameRequest, FrameUpdateRequest, HeadlessOpenData, HeadlessRequest, MonitorInfo, RenderMode,
-        ResizeDirection, VideoMode, WindowButton, WindowRequest, WindowStateAll,
+        CursorIcon, FocusIndicator, FrameId, FrameRequest, FrameUpdateRequest, HeadlessOpenData, HeadlessRequest, MonitorInfo, RenderMode,
+        RendererInfo, ResizeDirection, VideoMode, WindowButton, WindowLevel, WindowRequest, WindowStateAll,
     },
-    Event, ViewProcessGen, ViewProcessOffline,
+    Event, MemoryPressureThresholds, ViewProcessGen, ViewProcessOffline,
 };
 
 use zng_view_api::{
     clipboard::{ClipboardData, ClipboardError, ClipboardType},
     config::KeyRepeatConfig,
     font::{FontFaceId, FontId, FontVariationName},
-    image::{ImageId, ImageLoadedData},
+    image::{EncodeOptions, ImageEncodeId, ImageId, ImageLoadedData},
 };
 pub(crate) use zng_view_api::{window::MonitorId as ApiMonitorId, window::WindowId as ApiWindowId, Controller, DeviceId as ApiDeviceId};
 
@@ -66,6 +67,9 @@ struct ViewProcessService {
 
     pending_frames: usize,
 
+    enabled_decoders: Option<Vec<Txt>>,
+    memory_pressure_thresholds: Option<MemoryPressureThresholds>,
+
     message_dialogs: Vec<(zng_view_api::dialog::DialogId, ResponderVar<MsgDialogResponse>)>,
     file_dialogs: Vec<(zng_view_api::dialog::DialogId, ResponderVar<FileDialogResponse>)>,
 }
@@ -177,6 +181,8 @@ impl VIEW_PROCESS {
             pixels: None,
             is_mask: false,
             done_signal: SignalOnce::new(),
+            frame_delay: Duration::ZERO,
+            frames: vec![],
         })));
         app.loading_images.push(Arc::downgrade(&img.0));
         Ok(img)
@@ -207,6 +213,8 @@ impl VIEW_PROCESS {
             pixels: None,
             is_mask: false,
             done_signal: SignalOnce::new(),
+            frame_delay: Duration::ZERO,
+            frames: vec![],
         })));
         app.loading_images.push(Arc::downgrade(&img.0));
         Ok(img)
@@ -235,6 +243,30 @@ impl VIEW_PROCESS {
         self.write().process.image_encoders()
     }
 
+    /// Restrict the image decoders that can be used by the view-process backend.
+    ///
+    /// Each string is the file extension of an enabled decoder, all [`image_decoders`] are enabled by default.
+    /// Images with a format that is not enabled fail to load with an error. The `formats` are remembered and
+    /// set again if the view-process respawns.
+    ///
+    /// [`image_decoders`]: VIEW_PROCESS::image_decoders
+    pub fn set_enabled_decoders(&self, formats: Vec<Txt>) -> Result<()> {
+        let mut me = self.write();
+        me.enabled_decoders = Some(formats.clone());
+        me.process.set_enabled_decoders(formats)
+    }
+
+    /// Set the renderers GPU memory usage thresholds that cause a [`MEMORY_PRESSURE_EVENT`].
+    ///
+    /// The `thresholds` are remembered and set again if the view-process respawns.
+    ///
+    /// [`MEMORY_PRESSURE_EVENT`]: crate::view_process::raw_events::MEMORY_PRESSURE_EVENT
+    pub fn set_memory_pressure_thresholds(&self, thresholds: MemoryPressureThresholds) -> Result<()> {
+        let mut me = self.write();
+        me.memory_pressure_thresholds = Some(thresholds);
+        me.process.set_memory_pressure_thresholds(thresholds)
+    }
+
     /// Number of frame send that have not finished rendering.
     ///
     /// This is the sum of pending frames for all renderers.
@@ -319,6 +351,8 @@ impl VIEW_PROCESS {
             encoding_images: vec![],
             frame_images: vec![],
             pending_frames: 0,
+            enabled_decoders: None,
+            memory_pressure_thresholds: None,
             message_dialogs: vec![],
             file_dialogs: vec![],
             extensions: ApiExtensions::default(),
@@ -359,6 +393,12 @@ impl VIEW_PROCESS {
         let mut me = self.write();
         me.extensions = extensions;
         me.process.handle_inited(gen);
+        if let Some(formats) = me.enabled_decoders.clone() {
+            let _ = me.process.set_enabled_decoders(formats);
+        }
+        if let Some(thresholds) = me.memory_pressure_thresholds {
+            let _ = me.process.set_memory_pressure_thresholds(thresholds);
+        }
     }
 
     pub(crate) fn on_headless_opened(
@@ -439,6 +479,28 @@ impl VIEW_PROCESS {
                 img.pixels = Some(Ok(data.pixels));
                 img.partial_pixels = None;
                 img.is_mask = data.is_mask;
+                img.frame_delay = data.frame_delay;
+                img.frames = data
+                    .frames
+                    .into_iter()
+                    .map(|f| {
+                        ViewImage(Arc::new(RwLock::new(ViewImageData {
+                            app_id: img.app_id,
+                            id: Some(f.id),
+                            generation: img.generation,
+                            size: data.size,
+                            partial_size: data.size,
+                            ppi: data.ppi,
+                            is_opaque: f.is_opaque,
+                            partial_pixels: None,
+                            pixels: Some(Ok(f.pixels)),
+                            is_mask: false,
+                            done_signal: SignalOnce::new_set(),
+                            frame_delay: f.delay,
+                            frames: vec![],
+                        })))
+                    })
+                    .collect();
                 img.done_signal.set();
             }
             Some(ViewImage(img))
@@ -479,6 +541,8 @@ impl VIEW_PROCESS {
             pixels: Some(Ok(data.pixels)),
             is_mask: data.is_mask,
             done_signal: SignalOnce::new_set(),
+            frame_delay: Duration::ZERO,
+            frames: vec![],
         })))
     }
 
@@ -493,16 +557,17 @@ impl VIEW_PROCESS {
         i.map(|i| ViewImage(app.frame_images.swap_remove(i).upgrade().unwrap()))
     }
 
-    pub(super) fn on_image_encoded(&self, id: ImageId, format: Txt, data: IpcBytes) {
-        self.on_image_encode_result(id, format, Ok(data));
+    pub(super) fn on_image_encoded(&self, id: ImageId, request: ImageEncodeId, format: Txt, data: IpcBytes) {
+        self.on_image_encode_result(id, request, format, Ok(data));
     }
-    pub(super) fn on_image_encode_error(&self, id: ImageId, format: Txt, error: Txt) {
-        self.on_image_encode_result(id, format, Err(EncodeError::Encode(error)));
+    pub(super) fn on_image_encode_error(&self, id: ImageId, request: ImageEncodeId, format: Txt, error: Txt) {
+        self.on_image_encode_result(id, request, format, Err(EncodeError::Encode(error)));
     }
-    fn on_image_encode_result(&self, id: ImageId, format: Txt, result: std::result::Result<IpcBytes, EncodeError>) {
+    fn on_image_encode_result(&self, id: ImageId, request: ImageEncodeId, format: Txt, result: std::result::Result<IpcBytes, EncodeError>) {
         let mut app = self.write();
         app.encoding_images.retain(move |r| {
-            let done = r.image_id == id && r.format == format;
+            // default options requests are `INVALID` and are resolved by image and format.
+            let done = r.request == request && r.image_id == id && r.format == format;
             if done {
                 for sender in &r.listeners {
                     let _ = sender.send(result.clone());
@@ -676,6 +741,16 @@ impl ViewWindow {
         self.0.call(|id, p| p.set_always_on_top(id, always_on_top))
     }
 
+    /// Set the window z-order level.
+    pub fn set_window_level(&self, level: WindowLevel) -> Result<()> {
+        self.0.call(|id, p| p.set_window_level(id, level))
+    }
+
+    /// Set the color scheme of the window chrome, `None` uses the system color scheme.
+    pub fn set_preferred_theme(&self, theme: Option<ColorScheme>) -> Result<()> {
+        self.0.call(|id, p| p.set_preferred_theme(id, theme))
+    }
+
     /// Set if the user can drag-move the window.
     pub fn set_movable(&self, movable: bool) -> Result<()> {
         self.0.call(|id, p| p.set_movable(id, movable))
@@ -686,6 +761,16 @@ impl ViewWindow {
         self.0.call(|id, p| p.set_resizable(id, resizable))
     }
 
+    /// Set the width/height ratio maintained when the user resizes the window, or `None` to remove the constraint.
+    pub fn set_aspect_ratio(&self, ratio: Option<f32>) -> Result<()> {
+        self.0.call(|id, p| p.set_aspect_ratio(id, ratio))
+    }
+
+    /// Set if the window restore rectangle is clamped to the current monitor, so that it is not partially off-screen.
+    pub fn set_clamp_to_monitor(&self, clamp: bool) -> Result<()> {
+        self.0.call(|id, p| p.set_clamp_to_monitor(id, clamp))
+    }
+
     /// Set the window icon.
     pub fn set_icon(&self, icon: Option<&ViewImage>) -> Result<()> {
         self.0.call(|id, p| {
@@ -702,6 +787,19 @@ impl ViewWindow {
         })
     }
 
+    /// Set the window icon from the last rendered frame of the headless `surface`.
+    ///
+    /// The icon is unset if the surface has not rendered a frame yet.
+    pub fn set_icon_from_surface(&self, surface: &ViewHeadless) -> Result<()> {
+        self.0.call(|id, p| {
+            if p.generation() == surface.0.generation {
+                p.set_window_icon_from_surface(id, surface.0.id)
+            } else {
+                Err(ViewProcessOffline)
+            }
+        })
+    }
+
     /// Set the window cursor icon and visibility.
     pub fn set_cursor(&self, cursor: Option<CursorIcon>) -> Result<()> {
         self.0.call(|id, p| p.set_cursor(id, cursor))
@@ -1011,6 +1109,11 @@ impl ViewRenderer {
         self.0.upgrade().map(|c| c.generation).ok_or(ViewProcessOffline)
     }
 
+    /// Get the graphics adapter and driver info of the renderer.
+    pub fn renderer_info(&self) -> Result<RendererInfo> {
+        self.call(|id, p| p.renderer_info(id))
+    }
+
     /// Use an image resource in the window renderer.
     ///
     /// Returns the image texture ID.
@@ -1092,6 +1195,26 @@ impl ViewRenderer {
         }
     }
 
+    /// Compute a hash of the pixels of the current rendered frame, without transferring the frame image.
+    ///
+    /// The hash is deterministic, it can be stored to detect render regressions in tests. Returns the
+    /// rendered frame ID and the hash, the frame ID is [`FrameId::INVALID`] if no frame was rendered yet.
+    pub fn frame_image_hash(&self) -> Result<(FrameId, u64)> {
+        self.call(|id, p| p.frame_image_hash(id))
+    }
+
+    /// Request a one-shot [`RAW_FRAME_PRESENTED_EVENT`] notification for when the `frame` is presented on screen.
+    ///
+    /// Unlike [`RAW_FRAME_RENDERED_EVENT`] this event is only notified after the frame is composited and presented, if the
+    /// `frame` or a newer frame was already presented the event notifies as soon as possible. Headless surfaces notify
+    /// when the frame finishes rendering.
+    ///
+    /// [`RAW_FRAME_PRESENTED_EVENT`]: crate::view_process::raw_events::RAW_FRAME_PRESENTED_EVENT
+    /// [`RAW_FRAME_RENDERED_EVENT`]: crate::view_process::raw_events::RAW_FRAME_RENDERED_EVENT
+    pub fn request_frame_presented(&self, frame: FrameId) -> Result<()> {
+        self.call(|id, p| p.request_frame_presented(id, frame))
+    }
+
     fn add_frame_image(app_id: AppId, id: ImageId) -> ViewImage {
         if id == ImageId::INVALID {
             ViewImage::dummy(None)
@@ -1109,6 +1232,8 @@ impl ViewRenderer {
                 pixels: None,
                 is_mask: false,
                 done_signal: SignalOnce::new(),
+                frame_delay: Duration::ZERO,
+                frames: vec![],
             })));
 
             app.loading_images.push(Arc::downgrade(&img.0));
@@ -1211,6 +1336,9 @@ struct ViewImageData {
     is_mask: bool,
 
     done_signal: SignalOnce,
+
+    frame_delay: Duration,
+    frames: Vec<ViewImage>,
 }
 impl Drop for ViewImageData {
     fn drop(&mut self) {
@@ -1316,6 +1444,41 @@ impl ViewImage {
         self.0.read().pixels.as_ref().and_then(|r| r.as_ref().ok()).cloned()
     }
 
+    /// Number of frames in the image, `1` for still images.
+    ///
+    /// Animated images (GIF, APNG, animated WebP) are decoded fully in the view-process, each frame
+    /// is a separate image that can be retrieved using [`frame`].
+    ///
+    /// [`frame`]: Self::frame
+    pub fn frame_count(&self) -> usize {
+        1 + self.0.read().frames.len()
+    }
+
+    /// Returns `true` if the image has more than one frame.
+    pub fn is_animated(&self) -> bool {
+        !self.0.read().frames.is_empty()
+    }
+
+    /// Gets the image for the frame `index`.
+    ///
+    /// Frame `0` is this image, returns `None` if `index` is out of bounds.
+    pub fn frame(&self, index: usize) -> Option<ViewImage> {
+        if index == 0 {
+            Some(self.clone())
+        } else {
+            self.0.read().frames.get(index - 1).cloned()
+        }
+    }
+
+    /// Time the first frame is presented before the next frame.
+    ///
+    /// Is zero for still images. Frame images returned by [`frame`] have their own delay.
+    ///
+    /// [`frame`]: Self::frame
+    pub fn frame_delay(&self) -> Duration {
+        self.0.read().frame_delay
+    }
+
     /// Returns the app that owns the view-process that is handling this image.
     pub fn app_id(&self) -> Option<AppId> {
         self.0.read().app_id
@@ -1349,6 +1512,8 @@ impl ViewImage {
             },
             is_mask: false,
             done_signal: SignalOnce::new_set(),
+            frame_delay: Duration::ZERO,
+            frames: vec![],
         })))
     }
 
@@ -1363,6 +1528,15 @@ impl ViewImage {
     ///
     /// [`image_encoders`]: VIEW_PROCESS::image_encoders
     pub async fn encode(&self, format: Txt) -> std::result::Result<IpcBytes, EncodeError> {
+        self.encode_opts(format, EncodeOptions::default()).await
+    }
+
+    /// Tries to encode the image to the format, with custom encoder options.
+    ///
+    /// The `format` must be one of the [`image_encoders`] supported by the view-process backend.
+    ///
+    /// [`image_encoders`]: VIEW_PROCESS::image_encoders
+    pub async fn encode_opts(&self, format: Txt, opts: EncodeOptions) -> std::result::Result<IpcBytes, EncodeError> {
         self.awaiter().await;
 
         if let Some(e) = self.error() {
@@ -1374,14 +1548,24 @@ impl ViewImage {
             if let Some(id) = img.id {
                 let mut app = VIEW_PROCESS.handle_write(img.app_id.unwrap());
 
-                app.process.encode_image(id, format.clone())?;
+                let request = if opts == EncodeOptions::default() {
+                    app.process.encode_image(id, format.clone())?;
+                    ImageEncodeId::INVALID
+                } else {
+                    app.process.encode_image_opts(id, format.clone(), opts)?
+                };
 
                 let (sender, receiver) = flume::bounded(1);
-                if let Some(entry) = app.encoding_images.iter_mut().find(|r| r.image_id == id && r.format == format) {
+                if let Some(entry) = app
+                    .encoding_images
+                    .iter_mut()
+                    .find(|r| r.request == request && r.image_id == id && r.format == format)
+                {
                     entry.listeners.push(sender);
                 } else {
                     app.encoding_images.push(EncodeRequest {
                         image_id: id,
+                        request,
                         format,
                         listeners: vec![sender],
                     });
@@ -1394,6 +1578,32 @@ impl ViewImage {
 
         receiver.recv_async().await?
     }
+
+    /// Tries to encode the image to the format, the encoded data is streamed in the returned receiver.
+    ///
+    /// Each package received is the continuation of the previous, an empty package indicates finish. If
+    /// encoding fails the receiver disconnects before the empty package. This avoids allocating the full
+    /// encoded data when it is only written to disk.
+    ///
+    /// The `format` must be one of the [`image_encoders`] supported by the view-process backend.
+    ///
+    /// [`image_encoders`]: VIEW_PROCESS::image_encoders
+    pub async fn encode_streaming(&self, format: Txt) -> std::result::Result<IpcBytesReceiver, EncodeError> {
+        self.awaiter().await;
+
+        if let Some(e) = self.error() {
+            return Err(EncodeError::Encode(e));
+        }
+
+        let img = self.0.read();
+        if let Some(id) = img.id {
+            let mut app = VIEW_PROCESS.handle_write(img.app_id.unwrap());
+            let receiver = app.process.encode_image_streaming(id, format)?;
+            Ok(receiver)
+        } else {
+            Err(EncodeError::Dummy)
+        }
+    }
 }
 
 /// Error returned by [`ViewImage::encode`].
@@ -1453,6 +1663,7 @@ impl WeakViewImage {
 
 struct EncodeRequest {
     image_id: ImageId,
+    request: ImageEncodeId,
     format: Txt,
     listeners: Vec<flume::Sender<std::result::Result<IpcBytes, EncodeError>>>,
 }
@@ -1502,6 +1713,8 @@ impl ViewClipboard {
                         pixels: None,
                         is_mask: false,
                         done_signal: SignalOnce::new(),
+                        frame_delay: Duration::ZERO,
+                        frames: vec![],
                     })));
                     app.loading_images.push(Arc::downgrade(&img.0));
                     Ok(Ok(img))
@@ -24,10 +24,12 @@ use zng_view_api::{
     keyboard::{Key, KeyCode, KeyLocation, KeyState},
     mouse::{ButtonState, MouseButton, MouseScrollDelta},
     touch::{TouchPhase, TouchUpdate},
-    window::{EventCause, FrameId, FrameWaitId, HeadlessOpenData, MonitorInfo, WindowStateAll},
+    window::{EventCause, FrameId, FrameWaitId, HeadlessOpenData, MonitorInfo, VideoMode, WindowStateAll},
     AxisId, Ime,
 };
 
+pub use zng_view_api::{MemoryPressureLevel, MemoryPressureThresholds};
+
 use crate::{
     event::{event, event_args},
     window::{MonitorId, WindowId},
@@ -130,6 +132,24 @@ event_args! {
         }
     }
 
+    /// Arguments for the [`RAW_FRAME_PRESENTED_EVENT`].
+    pub struct RawFramePresentedArgs {
+        /// Window that presented the frame.
+        pub window_id: WindowId,
+
+        /// Frame requested by [`ViewRenderer::request_frame_presented`].
+        ///
+        /// [`ViewRenderer::request_frame_presented`]: crate::view_process::ViewRenderer::request_frame_presented
+        pub frame_id: FrameId,
+
+        ..
+
+        /// Broadcast to all widgets.
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.search_all();
+        }
+    }
+
     /// Arguments for the [`RAW_WINDOW_CHANGED_EVENT`].
     pub struct RawWindowChangedArgs {
         /// Window that has moved, resized or has a state change.
@@ -227,6 +247,22 @@ event_args! {
         }
     }
 
+    /// Arguments for the [`RAW_WINDOW_VIDEO_MODE_CHANGED_EVENT`].
+    pub struct RawWindowVideoModeChangedArgs {
+        /// Window that changed video mode.
+        pub window_id: WindowId,
+
+        /// Actual exclusive fullscreen video mode, or `None` if the window is not in exclusive fullscreen.
+        pub mode: Option<VideoMode>,
+
+        ..
+
+        /// Broadcast to all widgets.
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.search_all();
+        }
+    }
+
     /// Arguments for the [`RAW_WINDOW_CLOSE_REQUESTED_EVENT`].
     pub struct RawWindowCloseRequestedArgs {
         /// Window that was requested to close.
@@ -641,6 +677,19 @@ event_args! {
             list.search_all()
         }
     }
+
+    /// Arguments for [`MEMORY_PRESSURE_EVENT`].
+    pub struct MemoryPressureArgs {
+        /// Pressure level.
+        pub level: MemoryPressureLevel,
+
+        ..
+
+        /// Broadcast to all widgets.
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.search_all()
+        }
+    }
 }
 
 event! {
@@ -668,6 +717,11 @@ event! {
     /// A frame finished rendering and was presented in a window.
     pub static RAW_FRAME_RENDERED_EVENT: RawFrameRenderedArgs;
 
+    /// A frame requested by [`ViewRenderer::request_frame_presented`] was presented on screen.
+    ///
+    /// [`ViewRenderer::request_frame_presented`]: crate::view_process::ViewRenderer::request_frame_presented
+    pub static RAW_FRAME_PRESENTED_EVENT: RawFramePresentedArgs;
+
     /// A window has finished initializing in the view-process.
     pub static RAW_WINDOW_OPEN_EVENT: RawWindowOpenArgs;
 
@@ -677,6 +731,12 @@ event! {
     /// A window or headless surface initialization failed in the view-process.
     pub static RAW_WINDOW_OR_HEADLESS_OPEN_ERROR_EVENT: RawWindowOrHeadlessOpenErrorArgs;
 
+    /// A window entered or exited exclusive fullscreen, or the exclusive video mode changed.
+    ///
+    /// The operating system can drop exclusive fullscreen, for example on *alt-tab*, apps that require exclusive
+    /// mode can observe this event to request it again.
+    pub static RAW_WINDOW_VIDEO_MODE_CHANGED_EVENT: RawWindowVideoModeChangedArgs;
+
     /// A window was requested to close.
     pub static RAW_WINDOW_CLOSE_REQUESTED_EVENT: RawWindowCloseRequestedArgs;
 
@@ -772,6 +832,9 @@ event! {
     /// System low memory warning, some platforms may kill the app if it does not release memory.
     pub static LOW_MEMORY_EVENT: LowMemoryArgs;
 
+    /// View-process renderers GPU memory usage is high, unused resources like cached images should be released.
+    pub static MEMORY_PRESSURE_EVENT: MemoryPressureArgs;
+
     /// Custom view-process extension event.
     pub static RAW_EXTENSION_EVENT: RawExtensionEventArgs;
 }
@@ -726,11 +726,20 @@ impl WidgetBoundsInfo {
 
     /// Gets the relative hit-test Z for `window_point` against the hit-test shapes rendered for the widget.
     pub fn hit_test_z(&self, window_point: PxPoint) -> RelativeHitZ {
+        self.hit_test_z_tag(window_point).0
+    }
+
+    /// Gets the relative hit-test Z and the hit tag for `window_point` against the hit-test shapes rendered for the widget.
+    ///
+    /// The tag is set if the top-most shape hit was pushed using [`HitTestBuilder::push_tag`].
+    ///
+    /// [`HitTestBuilder::push_tag`]: crate::render::HitTestBuilder::push_tag
+    pub fn hit_test_z_tag(&self, window_point: PxPoint) -> (RelativeHitZ, Option<u64>) {
         let m = self.0.lock();
         if m.hit_clips.is_hit_testable() {
             m.hit_clips.hit_test_z(&m.inner_transform, window_point)
         } else {
-            RelativeHitZ::NoHit
+            (RelativeHitZ::NoHit, None)
         }
     }
 
@@ -1581,10 +1590,11 @@ impl WidgetInfo {
     /// A hit happens if the point is inside [`inner_bounds`] and at least one hit-test shape rendered for the widget contains the point.
     ///
     /// [`inner_bounds`]: WidgetInfo::inner_bounds
-    fn hit_test_z(&self, point: PxPoint) -> Option<ZIndex> {
+    fn hit_test_z(&self, point: PxPoint) -> Option<(ZIndex, Option<u64>)> {
         let bounds = &self.info().bounds_info;
         if bounds.inner_bounds().contains(point) {
-            let z = match bounds.hit_test_z(point) {
+            let (z, tag) = bounds.hit_test_z_tag(point);
+            let z = match z {
                 RelativeHitZ::NoHit => None,
                 RelativeHitZ::Back => bounds.render_info().map(|i| (i.seg_id, i.back)),
                 RelativeHitZ::Over(w) => self
@@ -1609,7 +1619,10 @@ impl WidgetInfo {
                         child = p;
                     }
 
-                    Some(ZIndex((z + self.tree.0.frame.read().widget_count_offsets.offset(seg_id)) as u32))
+                    Some((
+                        ZIndex((z + self.tree.0.frame.read().widget_count_offsets.offset(seg_id)) as u32),
+                        tag,
+                    ))
                 }
                 None => None,
             }
@@ -1705,9 +1718,10 @@ impl WidgetInfo {
         let mut hits: Vec<_> = self
             .inner_contains(point)
             .filter_map(|w| {
-                w.hit_test_z(point).map(|z| HitInfo {
+                w.hit_test_z(point).map(|(z, tag)| HitInfo {
                     widget_id: w.id(),
                     z_index: z,
+                    tag,
                 })
             })
             .collect();
@@ -2202,6 +2216,11 @@ pub struct HitInfo {
 
     /// Z-index of the hit.
     pub z_index: ZIndex,
+
+    /// Tag of the hit area, if the top-most shape hit in the widget was pushed using [`HitTestBuilder::push_tag`].
+    ///
+    /// [`HitTestBuilder::push_tag`]: crate::render::HitTestBuilder::push_tag
+    pub tag: Option<u64>,
 }
 
 /// A hit-test result.
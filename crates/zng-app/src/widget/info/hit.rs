@@ -47,6 +47,10 @@ impl HitTestClips {
         self.items.push(HitTestItem::Hit(HitTestPrimitive::Ellipse(center, radii)));
     }
 
+    pub fn push_tag(&mut self, tag: u64, area: PxBox) {
+        self.items.push(HitTestItem::Tag(HitTestPrimitive::Rect(area), tag));
+    }
+
     pub fn push_clip_ellipse(&mut self, center: PxPoint, radii: PxSize, clip_out: bool) {
         self.items
             .push(HitTestItem::Clip(HitTestPrimitive::Ellipse(center, radii), clip_out));
@@ -74,16 +78,17 @@ impl HitTestClips {
         HitChildIndex(self.segments.id(), self.items.len() - 1)
     }
 
-    /// Hit-test the `point` against the items, returns the relative Z of the hit.
-    pub fn hit_test_z(&self, inner_transform: &PxTransform, window_point: PxPoint) -> RelativeHitZ {
+    /// Hit-test the `point` against the items, returns the relative Z of the hit and the tag if the top hit was a tag area.
+    pub fn hit_test_z(&self, inner_transform: &PxTransform, window_point: PxPoint) -> (RelativeHitZ, Option<u64>) {
         let mut z = RelativeHitZ::NoHit;
+        let mut tag = None;
         let mut child = None;
 
         let mut transform_stack = vec![];
         let mut current_transform = inner_transform;
         let mut local_point = match inv_transform_point(current_transform, window_point) {
             Some(p) => p,
-            None => return RelativeHitZ::NoHit,
+            None => return (RelativeHitZ::NoHit, None),
         };
 
         let mut items = self.items.iter();
@@ -97,6 +102,17 @@ impl HitTestClips {
                         } else {
                             RelativeHitZ::Back
                         };
+                        tag = None;
+                    }
+                }
+                HitTestItem::Tag(prim, t) => {
+                    if prim.contains(local_point) {
+                        z = if let Some(inner) = child {
+                            RelativeHitZ::Over(inner)
+                        } else {
+                            RelativeHitZ::Back
+                        };
+                        tag = Some(*t);
                     }
                 }
 
@@ -176,10 +192,10 @@ impl HitTestClips {
 
         if let (RelativeHitZ::Over(w), Some(c)) = (z, child) {
             if w == c {
-                return RelativeHitZ::Front;
+                return (RelativeHitZ::Front, tag);
             }
         }
-        z
+        (z, tag)
     }
 
     pub fn update_transform(&mut self, value: FrameValueUpdate<PxTransform>) {
@@ -320,6 +336,7 @@ impl HitTestPrimitive {
 #[derive(Debug)]
 enum HitTestItem {
     Hit(HitTestPrimitive),
+    Tag(HitTestPrimitive, u64),
 
     Clip(HitTestPrimitive, bool),
     PopClip,
@@ -508,3 +525,76 @@ impl ParallelSegmentOffsets {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use zng_layout::unit::{Px, PxRect};
+
+    use super::*;
+    use crate::widget::info::WidgetBoundsInfo;
+
+    fn rect(x: i32, y: i32, w: i32, h: i32) -> PxBox {
+        PxRect::new(PxPoint::new(Px(x), Px(y)), PxSize::new(Px(w), Px(h))).to_box2d()
+    }
+
+    fn hit_test(clips: HitTestClips, x: i32, y: i32) -> (RelativeHitZ, Option<u64>) {
+        let bounds = WidgetBoundsInfo::new();
+        bounds.set_hit_clips(clips);
+        bounds.hit_test_z_tag(PxPoint::new(Px(x), Px(y)))
+    }
+
+    #[test]
+    fn hit_test_z_tag() {
+        let mut clips = HitTestClips::default();
+        clips.push_rect(rect(0, 0, 100, 100));
+        clips.push_tag(1, rect(0, 0, 50, 50));
+        assert_eq!(hit_test(clips, 10, 10), (RelativeHitZ::Back, Some(1)));
+
+        let mut clips = HitTestClips::default();
+        clips.push_rect(rect(0, 0, 100, 100));
+        clips.push_tag(1, rect(0, 0, 50, 50));
+        assert_eq!(hit_test(clips, 70, 70), (RelativeHitZ::Back, None));
+
+        assert_eq!(hit_test(HitTestClips::default(), 10, 10), (RelativeHitZ::NoHit, None));
+    }
+
+    #[test]
+    fn hit_test_z_tag_order() {
+        let clips = || {
+            let mut clips = HitTestClips::default();
+            clips.push_tag(1, rect(0, 0, 100, 100));
+            clips.push_tag(2, rect(50, 50, 50, 50));
+            clips.push_rect(rect(80, 80, 20, 20));
+            clips
+        };
+
+        // the top-most shape defines the tag.
+        assert_eq!(hit_test(clips(), 10, 10), (RelativeHitZ::Back, Some(1)));
+        assert_eq!(hit_test(clips(), 60, 60), (RelativeHitZ::Back, Some(2)));
+        // untagged shape over the tags.
+        assert_eq!(hit_test(clips(), 90, 90), (RelativeHitZ::Back, None));
+    }
+
+    #[test]
+    fn hit_test_z_tag_over_child() {
+        let clips = || {
+            let mut clips = HitTestClips::default();
+            clips.push_tag(1, rect(0, 0, 100, 100));
+            let _ = clips.push_child(WidgetId::named("child"));
+            clips.push_tag(2, rect(50, 50, 50, 50));
+            clips
+        };
+
+        assert_eq!(hit_test(clips(), 10, 10), (RelativeHitZ::Back, Some(1)));
+        assert_eq!(hit_test(clips(), 60, 60), (RelativeHitZ::Front, Some(2)));
+    }
+
+    #[test]
+    fn hit_test_z_tag_clipped() {
+        let mut clips = HitTestClips::default();
+        clips.push_clip_rect(rect(0, 0, 50, 50), false);
+        clips.push_tag(1, rect(0, 0, 100, 100));
+        clips.pop_clip();
+        assert_eq!(hit_test(clips, 70, 70), (RelativeHitZ::NoHit, None));
+    }
+}
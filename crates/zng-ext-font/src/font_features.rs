@@ -1771,7 +1771,7 @@ impl fmt::Display for FontVariationName {
 /// A small map of font variations.
 ///
 /// Use [`font_variations!`] to manually initialize.
-#[derive(Default, Clone, PartialEq)]
+#[derive(Default, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct FontVariations(Vec<(FontVariationName, f32)>);
 impl FontVariations {
     /// New empty.
@@ -26,6 +26,7 @@ use node::*;
 use zng_app::{
     event::{event, event_args},
     handler::async_clmv,
+    timer::{DeadlineVar, TIMERS},
     update::UPDATES,
     AppExtension, DInstant, INSTANT,
 };
@@ -168,30 +169,62 @@ pub struct HotStatus {
     pub building: Option<DInstant>,
 
     /// Last rebuild and reload result.
-    ///
-    /// is `Ok(build_duration)` or `Err(build_error)`.
-    pub last_build: Result<Duration, BuildError>,
+    pub last_build: HotBuildStatus,
 
     /// Number of times the dynamically library was rebuilt (successfully or with error).
     pub rebuild_count: usize,
 }
 impl HotStatus {
-    /// Gets the build time if the last build succeeded.
+    /// Gets the build time if the last build and load succeeded.
     pub fn ok(&self) -> Option<Duration> {
-        self.last_build.as_ref().ok().copied()
+        match &self.last_build {
+            HotBuildStatus::Built(d) => Some(*d),
+            _ => None,
+        }
     }
 
     /// If the last build was cancelled.
     pub fn is_cancelled(&self) -> bool {
-        matches!(&self.last_build, Err(BuildError::Cancelled))
+        matches!(&self.last_build, HotBuildStatus::BuildError(BuildError::Cancelled))
     }
 
-    /// Gets the last build error if it failed and was not cancelled.
+    /// Gets the last build or load error if it failed and was not cancelled.
     pub fn err(&self) -> Option<&BuildError> {
-        self.last_build.as_ref().err().filter(|e| !matches!(e, BuildError::Cancelled))
+        self.build_err().or_else(|| self.load_err())
+    }
+
+    /// Gets the last build error if the build failed and was not cancelled.
+    pub fn build_err(&self) -> Option<&BuildError> {
+        match &self.last_build {
+            HotBuildStatus::BuildError(e) if !matches!(e, BuildError::Cancelled) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// Gets the last load error if the build succeeded but the dynamic library failed to load.
+    pub fn load_err(&self) -> Option<&BuildError> {
+        match &self.last_build {
+            HotBuildStatus::LoadError(e) => Some(e),
+            _ => None,
+        }
     }
 }
 
+/// Result of the last rebuild and reload of a monitored dynamic library crate.
+#[derive(Clone, PartialEq, Debug)]
+pub enum HotBuildStatus {
+    /// Rebuild and reload succeeded, in the build duration.
+    ///
+    /// Is `Duration::MAX` before the first rebuild.
+    Built(Duration),
+    /// Rebuild failed or was cancelled.
+    BuildError(BuildError),
+    /// Rebuild succeeded, but the dynamic library failed to load.
+    ///
+    /// This can be a missing `zng_hot_entry` symbol or other dynamic library incompatibility.
+    LoadError(BuildError),
+}
+
 /// Hot reload app extension.
 ///
 /// # Events
@@ -222,7 +255,7 @@ impl AppExtension for HotReloadManager {
                 status.push(HotStatus {
                     manifest_dir: entry.manifest_dir.into(),
                     building: None,
-                    last_build: Ok(Duration::MAX),
+                    last_build: HotBuildStatus::Built(Duration::MAX),
                     rebuild_count: 0,
                 });
             }
@@ -234,13 +267,24 @@ impl AppExtension for HotReloadManager {
         if let Some(args) = zng_ext_fs_watcher::FS_CHANGES_EVENT.on(update) {
             for (manifest_dir, watched) in self.libs.iter_mut() {
                 if args.changes_for_path(manifest_dir.as_ref()).next().is_some() {
-                    watched.rebuild((*manifest_dir).into(), self.static_patch.get_or_insert_with(StaticPatch::capture));
+                    // restart debounce, rebuild after the burst of changes settles.
+                    watched.rebuild_timer = Some(TIMERS.deadline(HOT_RELOAD_SV.read().debounce));
                 }
             }
         }
     }
 
     fn update_preview(&mut self) {
+        for (manifest_dir, watched) in self.libs.iter_mut() {
+            if let Some(timer) = watched.rebuild_timer.take() {
+                if timer.get().has_elapsed() {
+                    watched.rebuild((*manifest_dir).into(), self.static_patch.get_or_insert_with(StaticPatch::capture));
+                } else {
+                    watched.rebuild_timer = Some(timer);
+                }
+            }
+        }
+
         for (manifest_dir, watched) in self.libs.iter_mut() {
             if let Some(b) = &watched.building {
                 if let Some(r) = b.rebuild_load.rsp() {
@@ -249,23 +293,32 @@ impl AppExtension for HotReloadManager {
                     let status_r = match r {
                         Ok(l) => {
                             lib = Some(l);
-                            Ok(build_time)
+                            HotBuildStatus::Built(build_time)
                         }
-                        Err(e) => {
+                        Err(RebuildLoadError::Build(e)) => {
                             if matches!(&e, BuildError::Cancelled) {
                                 tracing::warn!("cancelled rebuild `{manifest_dir}`");
                             } else {
                                 tracing::error!("failed rebuild `{manifest_dir}`, {e}");
                             }
-                            Err(e)
+                            HotBuildStatus::BuildError(e)
+                        }
+                        Err(RebuildLoadError::Load(e)) => {
+                            tracing::error!("failed reload `{manifest_dir}`, {e}");
+                            HotBuildStatus::LoadError(e)
                         }
                     };
                     if let Some(lib) = lib {
-                        tracing::info!("rebuilt and reloaded `{manifest_dir}` in {build_time:?}");
+                        if b.is_reapply {
+                            tracing::info!("reloaded `{manifest_dir}`");
+                        } else {
+                            tracing::info!("rebuilt and reloaded `{manifest_dir}` in {build_time:?}");
+                        }
                         HOT_RELOAD.set(lib.clone());
                         HOT_RELOAD_EVENT.notify(HotReloadArgs::now(lib));
                     }
 
+                    let is_reapply = b.is_reapply;
                     watched.building = None;
 
                     let manifest_dir = *manifest_dir;
@@ -273,7 +326,9 @@ impl AppExtension for HotReloadManager {
                         let s = s.to_mut().iter_mut().find(|s| s.manifest_dir == manifest_dir).unwrap();
                         s.building = None;
                         s.last_build = status_r;
-                        s.rebuild_count += 1;
+                        if !is_reapply {
+                            s.rebuild_count += 1;
+                        }
                     });
 
                     if mem::take(&mut watched.rebuild_again) {
@@ -293,8 +348,20 @@ impl AppExtension for HotReloadManager {
             }
         }
 
+        let reapply: HashSet<Txt> = sv.reapply_requests.drain(..).collect();
         let requests: HashSet<Txt> = sv.rebuild_requests.drain(..).collect();
         drop(sv);
+        for r in reapply {
+            if let Some(watched) = self.libs.get_mut(r.as_str()) {
+                if let Some(lib) = HOT_RELOAD.lib(r.as_str()) {
+                    watched.reload(r, lib.build_path, self.static_patch.get_or_insert_with(StaticPatch::capture));
+                } else {
+                    tracing::error!("cannot reapply `{r}`, no successful build");
+                }
+            } else {
+                tracing::error!("cannot reapply `{r}`, unknown");
+            }
+        }
         for r in requests {
             if let Some(watched) = self.libs.get_mut(r.as_str()) {
                 watched.rebuild(r, self.static_patch.get_or_insert_with(StaticPatch::capture));
@@ -307,7 +374,14 @@ impl AppExtension for HotReloadManager {
 
 type RebuildVar = ResponseVar<Result<PathBuf, BuildError>>;
 
-type RebuildLoadVar = ResponseVar<Result<HotLib, BuildError>>;
+type RebuildLoadVar = ResponseVar<Result<HotLib, RebuildLoadError>>;
+
+/// Error of a rebuild and reload.
+#[derive(Clone, PartialEq, Debug)]
+enum RebuildLoadError {
+    Build(BuildError),
+    Load(BuildError),
+}
 
 /// Arguments for custom rebuild runners.
 ///
@@ -454,6 +528,22 @@ impl HOT_RELOAD {
         HOT_RELOAD_SV.write().rebuilders.get_mut().push(Box::new(rebuilder));
     }
 
+    /// Gets the debounce interval applied to file changes before a rebuild starts.
+    ///
+    /// Is 250ms by default.
+    pub fn debounce(&self) -> Duration {
+        HOT_RELOAD_SV.read().debounce
+    }
+
+    /// Set the debounce interval applied to file changes before a rebuild starts.
+    ///
+    /// Changes inside a hot library directory restart the interval, the rebuild only starts after no change
+    /// is observed for the `debounce` duration. This avoids redundant rebuilds for editors that save multiple files
+    /// or touch the same file multiple times.
+    pub fn set_debounce(&self, debounce: Duration) {
+        HOT_RELOAD_SV.write().debounce = debounce;
+    }
+
     /// Request a rebuild, if `manifest_dir` is a hot library.
     ///
     /// Note that changes inside the directory already trigger a rebuild automatically.
@@ -462,13 +552,24 @@ impl HOT_RELOAD {
         UPDATES.update(None);
     }
 
+    /// Request a reload of the last successfully built dynamic library for `manifest_dir`, without rebuilding.
+    ///
+    /// The last built dylib is copied and loaded again, [`HOT_RELOAD_EVENT`] notifies when it has loaded. This is
+    /// useful to recover the hot nodes after a failed rebuild when the dylib on disk is still valid.
+    ///
+    /// Does nothing if `manifest_dir` is not a hot library, is currently rebuilding, or was never successfully rebuilt.
+    pub fn reapply_last(&self, manifest_dir: impl Into<Txt>) {
+        HOT_RELOAD_SV.write().reapply_requests.push(manifest_dir.into());
+        UPDATES.update(None);
+    }
+
     /// Request a rebuild cancel for the current building `manifest_dir`.
     pub fn cancel(&self, manifest_dir: impl Into<Txt>) {
         HOT_RELOAD_SV.write().cancel_requests.push(manifest_dir.into());
         UPDATES.update(None);
     }
 
-    pub(crate) fn lib(&self, manifest_dir: &'static str) -> Option<HotLib> {
+    pub(crate) fn lib(&self, manifest_dir: &str) -> Option<HotLib> {
         HOT_RELOAD_SV
             .read()
             .libs
@@ -492,6 +593,8 @@ app_local! {
             status: zng_var::var(vec![]),
             rebuild_requests: vec![] ,
             cancel_requests: vec![] ,
+            reapply_requests: vec![],
+            debounce: 250.ms(),
         }
     };
 }
@@ -504,45 +607,57 @@ struct HotReloadService {
     status: ArcVar<Vec<HotStatus>>,
     rebuild_requests: Vec<Txt>,
     cancel_requests: Vec<Txt>,
+    reapply_requests: Vec<Txt>,
+    debounce: Duration,
 }
 impl HotReloadService {
     fn rebuild_reload(&mut self, manifest_dir: Txt, static_patch: &StaticPatch) -> (RebuildLoadVar, SignalOnce) {
         let (rebuild, cancel) = self.rebuild(manifest_dir.clone());
         let rebuild_load = zng_task::respond(async_clmv!(static_patch, {
-            let build_path = rebuild.wait_into_rsp().await?;
+            let build_path = rebuild.wait_into_rsp().await.map_err(RebuildLoadError::Build)?;
+            Self::load(static_patch, manifest_dir, build_path)
+                .await
+                .map_err(RebuildLoadError::Load)
+        }));
+        (rebuild_load, cancel)
+    }
 
-            // copy dylib to not block the next rebuild
-            let file_name = match build_path.file_name() {
-                Some(f) => f.to_string_lossy(),
-                None => return Err(std::io::Error::new(std::io::ErrorKind::NotFound, "dylib path does not have a file name").into()),
-            };
+    fn reload(&mut self, manifest_dir: Txt, build_path: PathBuf, static_patch: &StaticPatch) -> RebuildLoadVar {
+        let load = Self::load(static_patch.clone(), manifest_dir, build_path);
+        zng_task::respond(async move { load.await.map_err(RebuildLoadError::Load) })
+    }
 
-            // cleanup previous session
-            for p in glob::glob(&format!("{}/zng-hot-{file_name}-*", build_path.parent().unwrap().display()))
-                .unwrap()
-                .flatten()
-            {
-                let _ = std::fs::remove_file(p);
-            }
+    async fn load(static_patch: StaticPatch, manifest_dir: Txt, build_path: PathBuf) -> Result<HotLib, BuildError> {
+        // copy dylib to not block the next rebuild
+        let file_name = match build_path.file_name() {
+            Some(f) => f.to_string_lossy().into_owned(),
+            None => return Err(std::io::Error::new(std::io::ErrorKind::NotFound, "dylib path does not have a file name").into()),
+        };
 
-            let mut unique_path = build_path.clone();
-            let ts = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_millis();
-            unique_path.set_file_name(format!("zng-hot-{file_name}-{ts:x}"));
-            std::fs::copy(&build_path, &unique_path)?;
-
-            let dylib = zng_task::wait(move || HotLib::new(&static_patch, manifest_dir, unique_path));
-            match zng_task::with_deadline(dylib, 2.secs()).await {
-                Ok(r) => r.map_err(Into::into),
-                Err(_) => Err(BuildError::Io(Arc::new(io::Error::new(
-                    io::ErrorKind::TimedOut,
-                    "hot dylib did not init after 2s",
-                )))),
-            }
-        }));
-        (rebuild_load, cancel)
+        // cleanup previous session
+        for p in glob::glob(&format!("{}/zng-hot-{file_name}-*", build_path.parent().unwrap().display()))
+            .unwrap()
+            .flatten()
+        {
+            let _ = std::fs::remove_file(p);
+        }
+
+        let mut unique_path = build_path.clone();
+        let ts = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis();
+        unique_path.set_file_name(format!("zng-hot-{file_name}-{ts:x}"));
+        std::fs::copy(&build_path, &unique_path)?;
+
+        let dylib = zng_task::wait(move || HotLib::new(&static_patch, manifest_dir, build_path, unique_path));
+        match zng_task::with_deadline(dylib, 2.secs()).await {
+            Ok(r) => r.map_err(Into::into),
+            Err(_) => Err(BuildError::Io(Arc::new(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "hot dylib did not init after 2s",
+            )))),
+        }
     }
 
     fn rebuild(&mut self, manifest_dir: Txt) -> (RebuildVar, SignalOnce) {
@@ -596,6 +711,7 @@ event! {
 struct WatchedLib {
     building: Option<BuildingLib>,
     rebuild_again: bool,
+    rebuild_timer: Option<DeadlineVar>,
 }
 impl WatchedLib {
     fn rebuild(&mut self, manifest_dir: Txt, static_path: &StaticPatch) {
@@ -621,6 +737,7 @@ impl WatchedLib {
                 start_time,
                 rebuild_load,
                 cancel_build,
+                is_reapply: false,
             });
 
             sv.status.modify(move |s| {
@@ -628,12 +745,36 @@ impl WatchedLib {
             });
         }
     }
+
+    fn reload(&mut self, manifest_dir: Txt, build_path: PathBuf, static_path: &StaticPatch) {
+        if self.building.is_some() {
+            tracing::error!("cannot reapply `{manifest_dir}`, is rebuilding");
+            return;
+        }
+
+        let start_time = INSTANT.now();
+        tracing::info!("reloading `{manifest_dir}`");
+
+        let mut sv = HOT_RELOAD_SV.write();
+        let rebuild_load = sv.reload(manifest_dir.clone(), build_path, static_path);
+        self.building = Some(BuildingLib {
+            start_time,
+            rebuild_load,
+            cancel_build: SignalOnce::new(),
+            is_reapply: true,
+        });
+
+        sv.status.modify(move |s| {
+            s.to_mut().iter_mut().find(|s| s.manifest_dir == manifest_dir).unwrap().building = Some(start_time);
+        });
+    }
 }
 
 struct BuildingLib {
     start_time: DInstant,
     rebuild_load: RebuildLoadVar,
     cancel_build: SignalOnce,
+    is_reapply: bool,
 }
 
 #[doc(hidden)]
@@ -647,6 +788,7 @@ pub enum HotEntryExchange {
 #[derive(Clone)]
 pub(crate) struct HotLib {
     manifest_dir: Txt,
+    build_path: PathBuf,
     lib: Arc<libloading::Library>,
     hot_entry: unsafe extern "C" fn(&&str, &&'static str, &mut LocalContext, &mut HotEntryExchange),
 }
@@ -663,7 +805,12 @@ impl fmt::Debug for HotLib {
     }
 }
 impl HotLib {
-    pub fn new(patch: &StaticPatch, manifest_dir: Txt, lib: impl AsRef<std::ffi::OsStr>) -> Result<Self, libloading::Error> {
+    pub fn new(
+        patch: &StaticPatch,
+        manifest_dir: Txt,
+        build_path: PathBuf,
+        lib: impl AsRef<std::ffi::OsStr>,
+    ) -> Result<Self, libloading::Error> {
         unsafe {
             // SAFETY: assuming the the hot lib was setup as the documented, this works,
             // even the `linkme` stuff does not require any special care.
@@ -678,6 +825,7 @@ impl HotLib {
 
             Ok(Self {
                 manifest_dir,
+                build_path,
                 hot_entry: *lib.get(b"zng_hot_entry")?,
                 lib: Arc::new(lib),
             })
@@ -25,7 +25,9 @@ use task::io::AsyncReadExt;
 use zng_app::{
     update::EventUpdate,
     view_process::{
-        raw_events::{LOW_MEMORY_EVENT, RAW_IMAGE_LOADED_EVENT, RAW_IMAGE_LOAD_ERROR_EVENT, RAW_IMAGE_METADATA_LOADED_EVENT},
+        raw_events::{
+            LOW_MEMORY_EVENT, MEMORY_PRESSURE_EVENT, RAW_IMAGE_LOADED_EVENT, RAW_IMAGE_LOAD_ERROR_EVENT, RAW_IMAGE_METADATA_LOADED_EVENT,
+        },
         ViewImage, VIEW_PROCESS, VIEW_PROCESS_INITED_EVENT,
     },
     widget::UiTaskWidget,
@@ -197,7 +199,7 @@ impl AppExtension for ImageManager {
                     }
                 } // else { *is loading, will continue normally in self.update_preview()* }
             }
-        } else if LOW_MEMORY_EVENT.on(update).is_some() {
+        } else if LOW_MEMORY_EVENT.on(update).is_some() || MEMORY_PRESSURE_EVENT.on(update).is_some() {
             IMAGES.clean_all();
         } else {
             self.event_preview_render(update);
@@ -2,6 +2,7 @@ use std::{
     env, fmt, fs, io, mem, ops,
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
 
 use once_cell::sync::OnceCell;
@@ -22,7 +23,7 @@ use zng_view_api::{image::ImageTextureId, ViewProcessOffline};
 
 use crate::render::ImageRenderWindowRoot;
 
-pub use zng_view_api::image::{ImageDataFormat, ImageDownscale, ImageMaskMode, ImagePpi};
+pub use zng_view_api::image::{EncodeOptions, ImageDataFormat, ImageDownscale, ImageMaskMode, ImagePpi};
 
 /// A custom proxy in [`IMAGES`].
 ///
@@ -93,6 +94,7 @@ pub type ImageVar = ReadOnlyArcVar<Img>;
 pub struct Img {
     pub(super) view: OnceCell<ViewImage>,
     render_ids: Arc<Mutex<Vec<RenderImage>>>,
+    frames: Arc<OnceCell<Vec<Img>>>,
     pub(super) done_signal: SignalOnce,
     pub(super) cache_key: Option<ImageHash>,
 }
@@ -106,6 +108,7 @@ impl Img {
         Img {
             view: OnceCell::new(),
             render_ids: Arc::default(),
+            frames: Arc::default(),
             done_signal: SignalOnce::new(),
             cache_key,
         }
@@ -119,6 +122,7 @@ impl Img {
         Img {
             view: v,
             render_ids: Arc::default(),
+            frames: Arc::default(),
             done_signal: sig,
             cache_key: None,
         }
@@ -187,6 +191,38 @@ impl Img {
         self.view.get().map(|v| v.is_mask()).unwrap_or(false)
     }
 
+    /// Returns the number of frames, `1` for still images or if it is not loaded.
+    pub fn frame_count(&self) -> usize {
+        self.view().map(|v| v.frame_count()).unwrap_or(1)
+    }
+
+    /// Returns `true` if the image is loaded and has more than one frame.
+    pub fn is_animated(&self) -> bool {
+        self.view().map(|v| v.is_animated()).unwrap_or(false)
+    }
+
+    /// Gets the image for the frame `index`.
+    ///
+    /// Frame `0` is this image, returns `None` if the image is not loaded or `index` is out of bounds. Frame images
+    /// are cached, so that renderers can reuse the frame textures.
+    pub fn frame(&self, index: usize) -> Option<Img> {
+        if index == 0 {
+            return Some(self.clone());
+        }
+        let view = self.view()?;
+        let frames = self
+            .frames
+            .get_or_init(|| (1..view.frame_count()).filter_map(|i| view.frame(i)).map(Img::new).collect());
+        frames.get(index - 1).cloned()
+    }
+
+    /// Returns the time the frame is presented before the next frame in animated images.
+    ///
+    /// Is zero for still images.
+    pub fn frame_delay(&self) -> Duration {
+        self.view.get().map(|v| v.frame_delay()).unwrap_or_default()
+    }
+
     /// Connection to the image resource, if it is loaded.
     pub fn view(&self) -> Option<&ViewImage> {
         self.view.get().filter(|&v| v.is_loaded())
@@ -277,6 +313,16 @@ impl Img {
         }
     }
 
+    /// Encode the image to the format, with custom encoder options.
+    pub async fn encode_opts(&self, format: Txt, opts: EncodeOptions) -> std::result::Result<zng_view_api::ipc::IpcBytes, EncodeError> {
+        self.done_signal.clone().await;
+        if let Some(e) = self.error() {
+            Err(EncodeError::Encode(e))
+        } else {
+            self.view.get().unwrap().encode_opts(format, opts).await
+        }
+    }
+
     /// Encode and write the image to `path`.
     ///
     /// The image format is guessed from the file extension.
@@ -541,6 +541,16 @@ pub trait HeadlessAppKeyboardExt {
     /// Does a key-down, key-up and updates.
     fn press_key(&mut self, window_id: WindowId, code: KeyCode, location: KeyLocation, key: Key);
 
+    /// Does a key-down and updates.
+    ///
+    /// The key stays pressed until [`release_key`] is called.
+    ///
+    /// [`release_key`]: Self::release_key
+    fn hold_key(&mut self, window_id: WindowId, code: KeyCode, location: KeyLocation, key: Key);
+
+    /// Does a key-up and updates.
+    fn release_key(&mut self, window_id: WindowId, code: KeyCode, location: KeyLocation, key: Key);
+
     /// Does a modifiers changed, key-down, key-up, reset modifiers and updates.
     fn press_modified_key(&mut self, window_id: WindowId, modifiers: ModifiersState, code: KeyCode, location: KeyLocation, key: Key);
 }
@@ -558,6 +568,16 @@ impl HeadlessAppKeyboardExt for HeadlessApp {
         let _ = self.update(false);
     }
 
+    fn hold_key(&mut self, window_id: WindowId, code: KeyCode, location: KeyLocation, key: Key) {
+        self.on_keyboard_input(window_id, code, location, key, KeyState::Pressed);
+        let _ = self.update(false);
+    }
+
+    fn release_key(&mut self, window_id: WindowId, code: KeyCode, location: KeyLocation, key: Key) {
+        self.on_keyboard_input(window_id, code, location, key, KeyState::Released);
+        let _ = self.update(false);
+    }
+
     fn press_modified_key(&mut self, window_id: WindowId, modifiers: ModifiersState, code: KeyCode, location: KeyLocation, key: Key) {
         if modifiers.is_empty() {
             self.press_key(window_id, code, location, key);
@@ -18,3 +18,5 @@ pub mod keyboard;
 pub mod mouse;
 pub mod pointer_capture;
 pub mod touch;
+
+mod tests;
@@ -22,7 +22,7 @@ use zng_app::{
         WidgetId, WIDGET,
     },
     window::WindowId,
-    AppExtension, DInstant, INSTANT,
+    AppExtension, DInstant, HeadlessApp, INSTANT,
 };
 use zng_app_context::app_local;
 use zng_ext_window::WINDOWS;
@@ -1507,3 +1507,51 @@ struct MouseService {
     hovered: ArcVar<Option<InteractionPath>>,
     position: ArcVar<Option<MousePosition>>,
 }
+
+/// Extension trait that adds mouse simulation methods to [`HeadlessApp`].
+///
+/// [`HeadlessApp`]: zng_app::HeadlessApp
+pub trait HeadlessAppMouseExt {
+    /// Notifies mouse moved event.
+    ///
+    /// Note that the app is not updated so the event is pending after this call.
+    fn on_mouse_move(&mut self, window_id: WindowId, position: DipPoint);
+
+    /// Notifies mouse input event.
+    ///
+    /// Note that the app is not updated so the event is pending after this call.
+    fn on_mouse_input(&mut self, window_id: WindowId, button: MouseButton, state: ButtonState);
+
+    /// Does a mouse move and updates.
+    fn move_cursor(&mut self, window_id: WindowId, position: DipPoint);
+
+    /// Does a mouse move, updates, then a button press, button release and updates.
+    fn click_at(&mut self, window_id: WindowId, position: DipPoint, button: MouseButton);
+}
+impl HeadlessAppMouseExt for HeadlessApp {
+    fn on_mouse_move(&mut self, window_id: WindowId, position: DipPoint) {
+        use zng_app::view_process::raw_events::*;
+
+        let args = RawMouseMovedArgs::now(window_id, DeviceId::virtual_mouse(), vec![], position);
+        RAW_MOUSE_MOVED_EVENT.notify(args);
+    }
+
+    fn on_mouse_input(&mut self, window_id: WindowId, button: MouseButton, state: ButtonState) {
+        use zng_app::view_process::raw_events::*;
+
+        let args = RawMouseInputArgs::now(window_id, DeviceId::virtual_mouse(), state, button);
+        RAW_MOUSE_INPUT_EVENT.notify(args);
+    }
+
+    fn move_cursor(&mut self, window_id: WindowId, position: DipPoint) {
+        self.on_mouse_move(window_id, position);
+        let _ = self.update(false);
+    }
+
+    fn click_at(&mut self, window_id: WindowId, position: DipPoint, button: MouseButton) {
+        self.move_cursor(window_id, position);
+        self.on_mouse_input(window_id, button, ButtonState::Pressed);
+        self.on_mouse_input(window_id, button, ButtonState::Released);
+        let _ = self.update(false);
+    }
+}
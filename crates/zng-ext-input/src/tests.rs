@@ -0,0 +1,127 @@
+#![cfg(test)]
+
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use zng_app::{
+    handler::app_hn,
+    widget::{
+        node::{match_node_leaf, UiNodeOp},
+        WidgetId,
+    },
+    HeadlessApp, APP,
+};
+use zng_ext_window::{HeadlessAppWindowExt as _, HeadlessMonitor, StartPosition, WindowManager, WindowRoot};
+use zng_layout::unit::DipPoint;
+use zng_view_api::{
+    keyboard::{Key, KeyCode, KeyLocation},
+    mouse::MouseButton,
+};
+
+use crate::{
+    focus::{FocusInfoBuilder, FocusManager},
+    gesture::{ClickArgs, GestureManager, CLICK_EVENT},
+    keyboard::{HeadlessAppKeyboardExt as _, KeyInputArgs, KeyboardManager, KEY_INPUT_EVENT},
+    mouse::{
+        HeadlessAppMouseExt as _, MouseClickArgs, MouseInputArgs, MouseManager, MOUSE_CLICK_EVENT, MOUSE_INPUT_EVENT, MOUSE_MOVE_EVENT,
+    },
+};
+
+type Trace = Arc<Mutex<Vec<String>>>;
+
+fn test_app() -> (HeadlessApp, Trace) {
+    let mut app = APP
+        .minimal()
+        .extend(WindowManager::default())
+        .extend(FocusManager::default())
+        .extend(MouseManager::default())
+        .extend(KeyboardManager::default())
+        .extend(GestureManager::default())
+        .run_headless(false);
+
+    let trace = Trace::default();
+    MOUSE_MOVE_EVENT
+        .on_event(app_hn!(trace, |_, _| {
+            trace.lock().push("mouse move".to_owned());
+        }))
+        .perm();
+    MOUSE_INPUT_EVENT
+        .on_event(app_hn!(trace, |args: &MouseInputArgs, _| {
+            trace.lock().push(format!("mouse input {:?} {:?}", args.button, args.state));
+        }))
+        .perm();
+    MOUSE_CLICK_EVENT
+        .on_event(app_hn!(trace, |args: &MouseClickArgs, _| {
+            trace.lock().push(format!("mouse click {:?}", args.button));
+        }))
+        .perm();
+    CLICK_EVENT
+        .on_event(app_hn!(trace, |args: &ClickArgs, _| {
+            trace.lock().push(format!("click {}", args.click_count));
+        }))
+        .perm();
+    KEY_INPUT_EVENT
+        .on_event(app_hn!(trace, |args: &KeyInputArgs, _| {
+            trace.lock().push(format!("key input {:?} {:?}", args.key_code, args.state));
+        }))
+        .perm();
+
+    let _ = app.update(false);
+
+    (app, trace)
+}
+
+fn test_window() -> WindowRoot {
+    WindowRoot::new(
+        WidgetId::new_unique(),
+        StartPosition::Default,
+        false,
+        false,
+        None,
+        HeadlessMonitor::default(),
+        true,
+        match_node_leaf(|op| {
+            if let UiNodeOp::Info { info } = op {
+                FocusInfoBuilder::new(info).scope(true);
+            }
+        }),
+    )
+}
+
+#[test]
+fn headless_mouse_click() {
+    let (mut app, trace) = test_app();
+    let window_id = app.open_window(async { test_window() });
+    trace.lock().clear();
+
+    app.move_cursor(window_id, DipPoint::new(10.into(), 10.into()));
+    assert_eq!(&*trace.lock(), &["mouse move"]);
+    trace.lock().clear();
+
+    app.click_at(window_id, DipPoint::new(10.into(), 10.into()), MouseButton::Left);
+    assert_eq!(
+        &*trace.lock(),
+        &[
+            "mouse input Left Pressed",
+            "mouse input Left Released",
+            "mouse click Left",
+            "click 1",
+        ]
+    );
+}
+
+#[test]
+fn headless_hold_key() {
+    let (mut app, trace) = test_app();
+    let window_id = app.open_window(async { test_window() });
+    app.focus_window(window_id);
+    let _ = app.update(false);
+    trace.lock().clear();
+
+    app.hold_key(window_id, KeyCode::KeyA, KeyLocation::Standard, Key::Char('a'));
+    assert_eq!(&*trace.lock(), &["key input KeyA Pressed"]);
+    trace.lock().clear();
+
+    app.release_key(window_id, KeyCode::KeyA, KeyLocation::Standard, Key::Char('a'));
+    assert_eq!(&*trace.lock(), &["key input KeyA Released"]);
+}
@@ -295,6 +295,18 @@ impl HeadedCtrl {
                 });
             }
 
+            if let Some(clamp) = self.vars.clamp_to_monitor().get_new() {
+                self.update_gen(move |view| {
+                    let _: Ignore = view.set_clamp_to_monitor(clamp);
+                });
+            }
+
+            if let Some(ratio) = self.vars.aspect_ratio().get_new() {
+                self.update_gen(move |view| {
+                    let _: Ignore = view.set_aspect_ratio(ratio);
+                });
+            }
+
             if let Some(resizable) = self.vars.resizable().get_new() {
                 self.update_gen(move |view| {
                     let _: Ignore = view.set_resizable(resizable);
@@ -487,9 +499,9 @@ impl HeadedCtrl {
             });
         }
 
-        if let Some(top) = self.vars.always_on_top().get_new() {
+        if let Some(level) = self.vars.window_level().get_new() {
             self.update_gen(move |view| {
-                let _: Ignore = view.set_always_on_top(top);
+                let _: Ignore = view.set_window_level(level);
             });
         }
 
@@ -531,13 +543,16 @@ impl HeadedCtrl {
         }
 
         if update_colors || self.vars.color_scheme().is_new() || self.parent_color_scheme.as_ref().map(|t| t.is_new()).unwrap_or(false) {
-            let scheme = self
+            let preferred = self
                 .vars
                 .color_scheme()
                 .get()
-                .or_else(|| self.parent_color_scheme.as_ref().map(|t| t.get()))
-                .unwrap_or_else(|| WINDOWS.system_colors_config().scheme);
+                .or_else(|| self.parent_color_scheme.as_ref().map(|t| t.get()));
+            let scheme = preferred.unwrap_or_else(|| WINDOWS.system_colors_config().scheme);
             self.vars.0.actual_color_scheme.set(scheme);
+            self.update_gen(move |view| {
+                let _: Ignore = view.set_preferred_theme(preferred);
+            });
         }
         if update_colors || self.vars.accent_color().is_new() || self.parent_accent_color.as_ref().map(|t| t.is_new()).unwrap_or(false) {
             let accent = self
@@ -721,12 +736,15 @@ impl HeadedCtrl {
 
                 self.state = Some(args.data.state.clone());
 
-                let scheme = self
+                let preferred = self
                     .vars
                     .color_scheme()
                     .get()
-                    .or_else(|| self.parent_color_scheme.as_ref().map(|t| t.get()))
-                    .unwrap_or_else(|| WINDOWS.system_colors_config().scheme);
+                    .or_else(|| self.parent_color_scheme.as_ref().map(|t| t.get()));
+                if preferred.is_some() {
+                    let _: Ignore = args.window.set_preferred_theme(preferred);
+                }
+                let scheme = preferred.unwrap_or_else(|| WINDOWS.system_colors_config().scheme);
                 self.vars.0.actual_color_scheme.set(scheme);
                 let accent = self
                     .vars
@@ -1128,8 +1146,10 @@ impl HeadedCtrl {
             video_mode: self.vars.video_mode().get(),
             visible: self.vars.visible().get(),
             taskbar_visible: self.vars.taskbar_visible().get(),
-            always_on_top: self.vars.always_on_top().get(),
+            level: self.vars.window_level().get(),
             movable: self.vars.movable().get(),
+            clamp_to_monitor: self.vars.clamp_to_monitor().get(),
+            aspect_ratio: self.vars.aspect_ratio().get(),
             resizable: self.vars.resizable().get(),
             enabled_buttons: self.vars.enabled_buttons().get(),
             icon: self
@@ -1254,8 +1274,10 @@ impl HeadedCtrl {
             video_mode: self.vars.video_mode().get(),
             visible: self.vars.visible().get(),
             taskbar_visible: self.vars.taskbar_visible().get(),
-            always_on_top: self.vars.always_on_top().get(),
+            level: self.vars.window_level().get(),
             movable: self.vars.movable().get(),
+            clamp_to_monitor: self.vars.clamp_to_monitor().get(),
+            aspect_ratio: self.vars.aspect_ratio().get(),
             resizable: self.vars.resizable().get(),
             enabled_buttons: self.vars.enabled_buttons().get(),
             icon: self
@@ -2163,6 +2185,8 @@ impl ContentCtrl {
                 scale_factor,
                 FontAntiAliasing::Default,
             );
+            frame.set_display_item_budget(self.vars.display_item_budget().get());
+            frame.set_reuse_budget(self.vars.reuse_budget().get());
 
             let frame = WIDGET.with_context(&mut self.root_ctx, WidgetUpdateMode::Bubble, || {
                 self.root.render(&mut frame);
@@ -25,7 +25,7 @@ use zng_view_api::{
     ViewProcessOffline,
 };
 
-pub use zng_view_api::window::{FocusIndicator, RenderMode, VideoMode, WindowButton, WindowState};
+pub use zng_view_api::window::{FocusIndicator, RenderMode, VideoMode, WindowButton, WindowLevel, WindowState};
 
 use crate::{HeadlessMonitor, WINDOW_Ext as _, WINDOWS};
 
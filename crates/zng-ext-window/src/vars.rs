@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use zng_app::{
+    render::FrameBuilder,
     widget::info::access::AccessEnabled,
     window::{MonitorId, WindowId, WINDOW},
 };
@@ -13,7 +14,7 @@ use zng_unique_id::IdSet;
 use zng_var::{merge_var, var, var_from, ArcVar, BoxedVar, ReadOnlyArcVar, Var};
 use zng_view_api::{
     config::{ColorScheme, ColorsConfig},
-    window::{CursorIcon, FocusIndicator, RenderMode, VideoMode, WindowButton, WindowState},
+    window::{CursorIcon, FocusIndicator, RenderMode, VideoMode, WindowButton, WindowLevel, WindowState},
 };
 
 use crate::{AutoSize, CursorSource, FrameCaptureMode, MonitorQuery, WindowIcon};
@@ -55,8 +56,11 @@ pub(super) struct WindowVarsData {
 
     resizable: ArcVar<bool>,
     movable: ArcVar<bool>,
+    clamp_to_monitor: ArcVar<bool>,
+    aspect_ratio: ArcVar<Option<f32>>,
 
     always_on_top: ArcVar<bool>,
+    window_level: ArcVar<WindowLevel>,
 
     visible: ArcVar<bool>,
     taskbar_visible: ArcVar<bool>,
@@ -74,6 +78,8 @@ pub(super) struct WindowVarsData {
     pub(super) is_loaded: ArcVar<bool>,
 
     frame_capture_mode: ArcVar<FrameCaptureMode>,
+    display_item_budget: ArcVar<Option<usize>>,
+    reuse_budget: ArcVar<Option<u32>>,
     pub(super) render_mode: ArcVar<RenderMode>,
 
     pub(super) access_enabled: ArcVar<AccessEnabled>,
@@ -133,8 +139,11 @@ impl WindowVars {
 
             resizable: var(true),
             movable: var(true),
+            clamp_to_monitor: var(false),
+            aspect_ratio: var(None),
 
             always_on_top: var(false),
+            window_level: var(WindowLevel::Normal),
 
             visible: var(true),
             taskbar_visible: var(true),
@@ -152,11 +161,24 @@ impl WindowVars {
             is_loaded: var(false),
 
             frame_capture_mode: var(FrameCaptureMode::Sporadic),
+            display_item_budget: var(if cfg!(debug_assertions) {
+                Some(FrameBuilder::DEFAULT_DISPLAY_ITEM_BUDGET)
+            } else {
+                None
+            }),
+            reuse_budget: var(None),
             render_mode: var(default_render_mode),
 
             access_enabled: var(AccessEnabled::empty()),
             system_shutdown_warn: var(Txt::from("")),
         });
+        vars.window_level
+            .bind_map_bidi(
+                &vars.always_on_top,
+                |l| *l == WindowLevel::AlwaysOnTop,
+                |&t| if t { WindowLevel::AlwaysOnTop } else { WindowLevel::Normal },
+            )
+            .perm();
         Self(vars)
     }
 
@@ -512,6 +534,26 @@ impl WindowVars {
         self.0.movable.clone()
     }
 
+    /// Defines if the window restore rectangle is clamped to the current monitor.
+    ///
+    /// When enabled a restore size larger than the monitor is reduced and the position is adjusted so that
+    /// the window is fully inside the monitor when it is in the `Normal` state.
+    ///
+    /// The default value is `false`.
+    pub fn clamp_to_monitor(&self) -> ArcVar<bool> {
+        self.0.clamp_to_monitor.clone()
+    }
+
+    /// Defines the width/height ratio the window maintains when it is resized by the user.
+    ///
+    /// When set, the dimension that changed the least in a user resize is adjusted to maintain the ratio
+    /// when the window is in the `Normal` state.
+    ///
+    /// The default value is `None`.
+    pub fn aspect_ratio(&self) -> ArcVar<Option<f32>> {
+        self.0.aspect_ratio.clone()
+    }
+
     /// Defines the enabled state of the window chrome buttons.
     pub fn enabled_buttons(&self) -> ArcVar<WindowButton> {
         self.0.enabled_buttons.clone()
@@ -522,10 +564,26 @@ impl WindowVars {
     /// Note this only applies to other windows that are not also "always-on-top".
     ///
     /// The default value is `false`.
+    ///
+    /// This variable is bound to [`window_level`], `true` is [`WindowLevel::AlwaysOnTop`].
+    ///
+    /// [`window_level`]: Self::window_level
     pub fn always_on_top(&self) -> ArcVar<bool> {
         self.0.always_on_top.clone()
     }
 
+    /// Defines the window z-order level.
+    ///
+    /// Windows can be set to stay on top or below other windows. The [`always_on_top`] variable
+    /// is bound to this one.
+    ///
+    /// The default value is [`WindowLevel::Normal`].
+    ///
+    /// [`always_on_top`]: Self::always_on_top
+    pub fn window_level(&self) -> ArcVar<WindowLevel> {
+        self.0.window_level.clone()
+    }
+
     /// Defines if the window is visible on the screen and in the task-bar.
     ///
     /// This variable is observed only after the first frame render, before that the window
@@ -591,7 +649,8 @@ impl WindowVars {
 
     /// Override the preferred color scheme.
     ///
-    /// If set to `None` the system preference is used, see [`actual_color_scheme`].
+    /// If set to `None` the system preference is used, see [`actual_color_scheme`]. If set the window chrome
+    /// (title bar and system controls) also uses the color scheme, when supported by the system.
     ///
     /// [`actual_color_scheme`]: Self::actual_color_scheme
     pub fn color_scheme(&self) -> ArcVar<Option<ColorScheme>> {
@@ -675,6 +734,24 @@ impl WindowVars {
         self.0.frame_capture_mode.clone()
     }
 
+    /// Defines the display items soft budget of the window frames.
+    ///
+    /// If a frame exceeds this number of display items a warning is logged, see [`FrameBuilder::display_item_budget`] for more details.
+    ///
+    /// The default value is [`FrameBuilder::DEFAULT_DISPLAY_ITEM_BUDGET`] in debug builds and `None` in release builds.
+    pub fn display_item_budget(&self) -> ArcVar<Option<usize>> {
+        self.0.display_item_budget.clone()
+    }
+
+    /// Defines the display list reuse budget of the window frames, in frames.
+    ///
+    /// Reuse ranges generated more than this number of full frames ago are regenerated, see [`FrameBuilder::reuse_budget`] for more details.
+    ///
+    /// The default value is `None`, no limit.
+    pub fn reuse_budget(&self) -> ArcVar<Option<u32>> {
+        self.0.reuse_budget.clone()
+    }
+
     /// Window actual render mode.
     ///
     /// The initial value is the [`default_render_mode`], it can update after the window is created, when the view-process
@@ -12,6 +12,8 @@ use std::fmt;
 use serde::{Deserialize, Serialize};
 use zng_txt::Txt;
 
+mod spdx;
+
 /// Represents a license and dependencies that use it.
 #[derive(Serialize, Deserialize, Clone)]
 pub struct LicenseUsed {
@@ -77,6 +79,34 @@ pub struct License {
     /// License text.
     pub text: Txt,
 }
+impl License {
+    /// If the license [`id`] is an [SPDX] expression equivalent to the `other` license id.
+    ///
+    /// The expressions are compared ignoring case, the order of `AND` and `OR` operands and redundant parentheses. Deprecated
+    /// GNU ids like `GPL-2.0+` are equivalent to their `-only` or `-or-later` form, other ids with the `+` suffix are only equivalent
+    /// to themselves. The deprecated `/` separator is equivalent to `OR`. License exceptions are not ignored, `Apache-2.0` is not equivalent to `Apache-2.0 WITH LLVM-exception`.
+    ///
+    /// If any of the ids is not a valid expression returns `true` only if the licenses are equal.
+    ///
+    /// ```
+    /// # use zng_tp_licenses::*;
+    /// let license = |id: &'static str| License { id: id.into(), name: "".into(), text: "".into() };
+    ///
+    /// assert!(license("MIT OR Apache-2.0").spdx_equivalent(&license("Apache-2.0 OR MIT")));
+    /// assert!(license("MIT/Apache-2.0").spdx_equivalent(&license("(Apache-2.0 OR MIT)")));
+    /// assert!(license("GPL-2.0+").spdx_equivalent(&license("GPL-2.0-or-later")));
+    /// assert!(!license("Apache-2.0").spdx_equivalent(&license("Apache-2.0 WITH LLVM-exception")));
+    /// ```
+    ///
+    /// [`id`]: Self::id
+    /// [SPDX]: https://spdx.github.io/spdx-spec/v2.3/SPDX-license-expressions/
+    pub fn spdx_equivalent(&self, other: &License) -> bool {
+        match (spdx::Expr::parse(&self.id), spdx::Expr::parse(&other.id)) {
+            (Some(a), Some(b)) => a == b,
+            _ => self == other,
+        }
+    }
+}
 
 /// Represents a project or package that uses a license.
 #[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Debug, Clone)]
@@ -108,6 +138,83 @@ pub fn merge_licenses(into: &mut Vec<LicenseUsed>, licenses: Vec<LicenseUsed>) {
     }
 }
 
+/// Merge `licenses` into `into`, licenses with [`License::spdx_equivalent`] ids are merged.
+///
+/// The license name and text of the first license is retained.
+///
+/// The licenses and users are not sorted, call [`sort_licenses`] after merging all licenses.
+pub fn merge_licenses_spdx(into: &mut Vec<LicenseUsed>, licenses: Vec<LicenseUsed>) {
+    for license in licenses {
+        if let Some(l) = into.iter_mut().find(|l| l.license.spdx_equivalent(&license.license)) {
+            for user in license.used_by {
+                if !l.used_by.contains(&user) {
+                    l.used_by.push(user);
+                }
+            }
+        } else {
+            into.push(license);
+        }
+    }
+}
+
+/// Difference between two license lists.
+///
+/// See [`diff_licenses`] for more details.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct LicenseDiff {
+    /// Users and license that are in the new list, but not in the old list.
+    pub added: Vec<UserLicense>,
+    /// Users and license that are in the old list, but not in the new list.
+    pub removed: Vec<UserLicense>,
+    /// Licenses that are in the new list, but not in the old list.
+    pub new_licenses: Vec<License>,
+    /// Licenses that are in the old list, but not in the new list.
+    pub removed_licenses: Vec<License>,
+}
+impl LicenseDiff {
+    /// If the lists are equivalent.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.new_licenses.is_empty() && self.removed_licenses.is_empty()
+    }
+}
+
+/// Compare the `old` and `new` licenses.
+///
+/// Users and licenses are compared by equality, a user that changed version or license is listed as removed
+/// and added. The users are sorted by name.
+///
+/// ```
+/// # use zng_tp_licenses::*;
+/// let mit = License { id: "MIT".into(), name: "MIT License".into(), text: "".into() };
+/// let user = |name: &'static str| User { name: name.into(), version: "1.0.0".into(), url: "".into() };
+///
+/// let old = vec![LicenseUsed { license: mit.clone(), used_by: vec![user("a"), user("b")] }];
+/// let new = vec![LicenseUsed { license: mit.clone(), used_by: vec![user("b"), user("c")] }];
+///
+/// let diff = diff_licenses(&old, &new);
+/// assert_eq!(diff.added, vec![UserLicense { user: user("c"), license: mit.clone() }]);
+/// assert_eq!(diff.removed, vec![UserLicense { user: user("a"), license: mit }]);
+/// assert!(diff.new_licenses.is_empty());
+/// ```
+pub fn diff_licenses(old: &[LicenseUsed], new: &[LicenseUsed]) -> LicenseDiff {
+    let old_users = user_licenses(old);
+    let new_users = user_licenses(new);
+    LicenseDiff {
+        added: new_users.iter().filter(|u| !old_users.contains(u)).cloned().collect(),
+        removed: old_users.iter().filter(|u| !new_users.contains(u)).cloned().collect(),
+        new_licenses: new
+            .iter()
+            .filter(|l| !old.iter().any(|o| o.license == l.license))
+            .map(|l| l.license.clone())
+            .collect(),
+        removed_licenses: old
+            .iter()
+            .filter(|l| !new.iter().any(|n| n.license == l.license))
+            .map(|l| l.license.clone())
+            .collect(),
+    }
+}
+
 /// Sort vec by license name, and users of each license by name.
 pub fn sort_licenses(l: &mut Vec<LicenseUsed>) {
     l.sort_by(|a, b| a.license.name.cmp(&b.license.name));
@@ -134,6 +241,82 @@ pub fn collect_cargo_about(about_cfg_path: &str) -> Vec<LicenseUsed> {
         return vec![];
     }
 
+    let json = cargo_about_json(about_cfg_path);
+    parse_cargo_about(&json).expect("error parsing `cargo about` output")
+}
+
+/// Calls [`cargo about`] for the crate, or reuses the output of a previous call if the dependencies did not change.
+///
+/// This method must be used in build scripts (`build.rs`). The `Cargo.lock` file of the workspace and the `about_cfg_path`
+/// file are hashed, the `cargo about` output and hash are written to `cache_dir`, if the hash matches on a subsequent
+/// build the cached output is used instead of calling `cargo about` again. If `cache_dir` is empty the [`OUT_DIR`] is used.
+///
+/// If the `Cargo.lock` file is not found this is the same as [`collect_cargo_about`].
+///
+/// Returns an empty vec if the [`DOCS_RS`] env var is set to any value or if `ZNG_TP_LICENSES=false` is set.
+///
+/// # Panics
+///
+/// Panics for any error, including `cargo about` errors and JSON deserialization errors.
+///
+/// [`cargo about`]: https://github.com/EmbarkStudios/cargo-about
+/// [`DOCS_RS`]: https://docs.rs/about/builds#detecting-docsrs
+/// [`OUT_DIR`]: https://doc.rust-lang.org/cargo/reference/environment-variables.html#environment-variables-cargo-sets-for-build-scripts
+#[cfg(feature = "build")]
+pub fn collect_cargo_about_cached(about_cfg_path: &str, cache_dir: &str) -> Vec<LicenseUsed> {
+    use std::{
+        hash::{Hash as _, Hasher as _},
+        path::{Path, PathBuf},
+    };
+
+    if std::env::var("DOCS_RS").is_ok() || std::env::var("ZNG_TP_LICENSES").unwrap_or_default() == "false" {
+        return vec![];
+    }
+
+    let manifest_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").expect("missing CARGO_MANIFEST_DIR"));
+    let lock = match manifest_dir.ancestors().map(|d| d.join("Cargo.lock")).find(|f| f.is_file()) {
+        Some(l) => std::fs::read(l).expect("error reading `Cargo.lock`"),
+        None => return collect_cargo_about(about_cfg_path),
+    };
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    lock.hash(&mut hasher);
+    if !about_cfg_path.is_empty() {
+        std::fs::read(manifest_dir.join(about_cfg_path))
+            .unwrap_or_default()
+            .hash(&mut hasher);
+    }
+    let hash = format!("{:x}", hasher.finish());
+
+    let cache_dir = if cache_dir.is_empty() {
+        PathBuf::from(std::env::var("OUT_DIR").expect("missing OUT_DIR"))
+    } else {
+        PathBuf::from(cache_dir)
+    };
+    let cache_dir: &Path = &cache_dir;
+    let hash_file = cache_dir.join("zng-tp-licenses.cargo-about.hash");
+    let json_file = cache_dir.join("zng-tp-licenses.cargo-about.json");
+
+    if std::fs::read_to_string(&hash_file).map(|h| h == hash).unwrap_or(false) {
+        if let Ok(json) = std::fs::read_to_string(&json_file) {
+            if let Ok(r) = parse_cargo_about(&json) {
+                return r;
+            }
+        }
+    }
+
+    let json = cargo_about_json(about_cfg_path);
+    let r = parse_cargo_about(&json).expect("error parsing `cargo about` output");
+
+    std::fs::create_dir_all(cache_dir).expect("error creating cache dir");
+    std::fs::write(&json_file, json).expect("error writing cache file");
+    std::fs::write(&hash_file, hash).expect("error writing cache file");
+
+    r
+}
+
+#[cfg(feature = "build")]
+fn cargo_about_json(about_cfg_path: &str) -> String {
     let mut cargo_about = std::process::Command::new("cargo");
     cargo_about
         .arg("about")
@@ -154,9 +337,7 @@ pub fn collect_cargo_about(about_cfg_path: &str) -> Vec<LicenseUsed> {
         output.status
     );
 
-    let json = String::from_utf8(output.stdout).unwrap();
-
-    parse_cargo_about(&json).expect("error parsing `cargo about` output")
+    String::from_utf8(output.stdout).unwrap()
 }
 
 /// Parse the output of [`cargo about`].
@@ -167,7 +348,7 @@ pub fn collect_cargo_about(about_cfg_path: &str) -> Vec<LicenseUsed> {
 /// cargo about generate -c .cargo/about.toml --format json --workspace --all-features
 /// ```
 ///
-/// See also [`collect_cargo_about`] that calls the command.
+/// See also [`collect_cargo_about`] and [`collect_cargo_about_cached`] that call the command.
 ///
 /// [`cargo about`]: https://github.com/EmbarkStudios/cargo-about
 #[cfg(feature = "build")]
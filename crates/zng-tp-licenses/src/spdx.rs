@@ -0,0 +1,197 @@
+//! Minimal SPDX license expression parser, used to compare license ids.
+
+use std::iter::Peekable;
+
+/// Normalized SPDX license expression.
+///
+/// License ids are uppercase and deprecated GNU ids are converted to the `-only`/`-or-later` form, other ids with the
+/// `+` suffix are kept as is, as they have no equivalent id. The operands
+/// of `AND` and `OR` are flattened, sorted and deduplicated, so equivalent expressions are equal.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Expr {
+    License(String),
+    With(String, String),
+    And(Vec<Expr>),
+    Or(Vec<Expr>),
+}
+impl Expr {
+    /// Parse and normalize the expression, returns `None` if it is not a valid expression.
+    ///
+    /// The deprecated `/` separator is parsed as `OR`.
+    pub(crate) fn parse(expr: &str) -> Option<Self> {
+        let tokens = tokenize(expr);
+        let mut tokens = tokens.iter().map(String::as_str).peekable();
+        let r = parse_or(&mut tokens)?;
+        if tokens.next().is_some() {
+            return None;
+        }
+        Some(r)
+    }
+}
+
+fn tokenize(expr: &str) -> Vec<String> {
+    let mut tokens = vec![];
+    let mut token = String::new();
+    for c in expr.chars() {
+        if c.is_whitespace() || matches!(c, '(' | ')' | '/') {
+            if !token.is_empty() {
+                tokens.push(std::mem::take(&mut token));
+            }
+            match c {
+                '(' | ')' => tokens.push(c.to_string()),
+                '/' => tokens.push("OR".to_owned()),
+                _ => {}
+            }
+        } else {
+            token.push(c);
+        }
+    }
+    if !token.is_empty() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+fn parse_or<'a, I: Iterator<Item = &'a str>>(tokens: &mut Peekable<I>) -> Option<Expr> {
+    let mut operands = vec![parse_and(tokens)?];
+    while tokens.peek().map(|t| t.eq_ignore_ascii_case("OR")).unwrap_or(false) {
+        tokens.next();
+        operands.push(parse_and(tokens)?);
+    }
+    Some(combine(operands, false))
+}
+
+fn parse_and<'a, I: Iterator<Item = &'a str>>(tokens: &mut Peekable<I>) -> Option<Expr> {
+    let mut operands = vec![parse_with(tokens)?];
+    while tokens.peek().map(|t| t.eq_ignore_ascii_case("AND")).unwrap_or(false) {
+        tokens.next();
+        operands.push(parse_with(tokens)?);
+    }
+    Some(combine(operands, true))
+}
+
+fn parse_with<'a, I: Iterator<Item = &'a str>>(tokens: &mut Peekable<I>) -> Option<Expr> {
+    let t = tokens.next()?;
+    if t == "(" {
+        let r = parse_or(tokens)?;
+        return if tokens.next()? == ")" { Some(r) } else { None };
+    }
+    let license = license_id(t)?;
+    if tokens.peek().map(|t| t.eq_ignore_ascii_case("WITH")).unwrap_or(false) {
+        tokens.next();
+        let exception = license_id(tokens.next()?)?;
+        Some(Expr::With(license, exception))
+    } else {
+        Some(Expr::License(license))
+    }
+}
+
+fn license_id(t: &str) -> Option<String> {
+    if t == "(" || t == ")" || ["AND", "OR", "WITH"].iter().any(|op| t.eq_ignore_ascii_case(op)) {
+        return None;
+    }
+    let mut id = t.to_ascii_uppercase();
+    let is_gnu = ["GPL-", "LGPL-", "AGPL-", "GFDL-"].iter().any(|p| id.starts_with(p));
+    if is_gnu {
+        if let Some(base) = id.strip_suffix('+') {
+            id = format!("{base}-OR-LATER");
+        } else if !id.ends_with("-ONLY") && !id.ends_with("-OR-LATER") {
+            id.push_str("-ONLY");
+        }
+    }
+    Some(id)
+}
+
+fn combine(operands: Vec<Expr>, and: bool) -> Expr {
+    let mut flat = Vec::with_capacity(operands.len());
+    for o in operands {
+        match o {
+            Expr::And(inner) if and => flat.extend(inner),
+            Expr::Or(inner) if !and => flat.extend(inner),
+            o => flat.push(o),
+        }
+    }
+    flat.sort();
+    flat.dedup();
+    if flat.len() == 1 {
+        flat.remove(0)
+    } else if and {
+        Expr::And(flat)
+    } else {
+        Expr::Or(flat)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(expr: &str) -> Expr {
+        Expr::parse(expr).unwrap_or_else(|| panic!("failed to parse `{expr}`"))
+    }
+
+    fn license(id: &str) -> Expr {
+        Expr::License(id.to_owned())
+    }
+
+    #[test]
+    fn parse_license() {
+        assert_eq!(parse("MIT"), license("MIT"));
+        assert_eq!(parse("mit"), license("MIT"));
+        assert_eq!(parse(" (MIT) "), license("MIT"));
+    }
+
+    #[test]
+    fn parse_precedence() {
+        assert_eq!(
+            parse("MIT OR Apache-2.0 AND Zlib"),
+            Expr::Or(vec![license("MIT"), Expr::And(vec![license("APACHE-2.0"), license("ZLIB")])])
+        );
+        assert_eq!(
+            parse("(MIT OR Apache-2.0) AND Zlib"),
+            Expr::And(vec![license("ZLIB"), Expr::Or(vec![license("APACHE-2.0"), license("MIT")])])
+        );
+    }
+
+    #[test]
+    fn parse_with() {
+        assert_eq!(
+            parse("Apache-2.0 WITH LLVM-exception OR MIT"),
+            Expr::Or(vec![
+                license("MIT"),
+                Expr::With("APACHE-2.0".to_owned(), "LLVM-EXCEPTION".to_owned())
+            ])
+        );
+    }
+
+    #[test]
+    fn normalize_operands() {
+        assert_eq!(parse("MIT OR Apache-2.0"), parse("Apache-2.0 OR MIT"));
+        assert_eq!(parse("MIT/Apache-2.0"), parse("Apache-2.0 OR MIT"));
+        assert_eq!(parse("MIT OR (Zlib OR Apache-2.0)"), parse("Apache-2.0 OR MIT OR Zlib"));
+        assert_eq!(parse("MIT AND MIT"), license("MIT"));
+        assert_ne!(parse("MIT OR Apache-2.0"), parse("MIT AND Apache-2.0"));
+    }
+
+    #[test]
+    fn normalize_gnu_ids() {
+        assert_eq!(parse("GPL-2.0"), license("GPL-2.0-ONLY"));
+        assert_eq!(parse("GPL-2.0+"), license("GPL-2.0-OR-LATER"));
+        assert_eq!(parse("LGPL-2.1-or-later"), license("LGPL-2.1-OR-LATER"));
+        assert_eq!(parse("AGPL-3.0-only"), license("AGPL-3.0-ONLY"));
+    }
+
+    #[test]
+    fn non_gnu_plus_is_kept() {
+        assert_eq!(parse("Apache-1.0+"), license("APACHE-1.0+"));
+        assert_ne!(parse("Apache-1.0+"), parse("Apache-1.0"));
+        assert_eq!(parse("MPL-2.0"), license("MPL-2.0"));
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for expr in ["", "MIT OR", "AND MIT", "(MIT", "MIT)", "MIT Apache-2.0", "MIT WITH", "()"] {
+            assert!(Expr::parse(expr).is_none(), "`{expr}` should not parse");
+        }
+    }
+}
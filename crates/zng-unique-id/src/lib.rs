@@ -16,7 +16,7 @@ use std::{
     sync::atomic::{AtomicU32, AtomicU64, Ordering},
 };
 
-use rayon::iter::{FromParallelIterator, IntoParallelIterator, IntoParallelRefIterator, IntoParallelRefMutIterator};
+use rayon::iter::{FromParallelIterator, IntoParallelIterator, IntoParallelRefIterator, IntoParallelRefMutIterator, ParallelExtend};
 
 #[cfg(feature = "named")]
 mod named;
@@ -33,9 +33,37 @@ pub use named::*;
 pub use paste::paste;
 
 /// Declare a new unique id type that is backed by a `NonZeroU32`.
+///
+/// See [`unique_id_64!`] for the optional `seed` and `atomic_option` declarations.
 #[macro_export]
 macro_rules! unique_id_32 {
-    ($(#[$attrs:meta])* $vis:vis struct $Type:ident $(< $T:ident $(:($($bounds:tt)+))? >)? $(: $ParentId:path)? ;) => {
+    (
+        $(#[$attrs:meta])* $vis:vis struct $Type:ident $(: $ParentId:path)? ;
+        $(seed { $seed:expr })?
+        atomic_option {
+            $(#[$a_attrs:meta])* $a_vis:vis struct $AtomicType:ident;
+        }
+    ) => {
+        $crate::unique_id_32! {
+            $(#[$attrs])* $vis struct $Type $(: $ParentId)? ;
+            $(seed { $seed })?
+        }
+        $crate::unique_id! {
+            atomic_option {
+                $(#[$a_attrs])* $a_vis struct $AtomicType($Type);
+            }
+            atomic {
+                std::sync::atomic::AtomicU32
+            }
+            literal {
+                u32
+            }
+        }
+    };
+    (
+        $(#[$attrs:meta])* $vis:vis struct $Type:ident $(< $T:ident $(:($($bounds:tt)+))? >)? $(: $ParentId:path)? ;
+        $(seed { $seed:expr })?
+    ) => {
        $crate::unique_id! {
             request {
                 $(#[$attrs])*
@@ -69,25 +97,98 @@ macro_rules! unique_id_32 {
                 std::sync::atomic::AtomicU32
             }
             next_id {
-                $crate::next_id32
+                $crate::next_id32_seeded
             }
             literal {
                 u32
             }
             to_hash {
-                $crate::un_hash32
+                $crate::hash32_seeded
             }
             to_sequential {
-                $crate::un_hash32
+                $crate::un_hash32_seeded
+            }
+            seed {
+                0 $(+ $seed)?
             }
        }
     }
 }
 
 /// Declare a new unique id type that is backed by a `NonZeroU64`.
+///
+/// # Atomic Option
+///
+/// Non-generic IDs can also declare an atomic optional ID type, it is backed by the atomic integer and
+/// uses `0` to represent `None`, the same niche used by `Option<Id>`.
+///
+/// ```
+/// # use zng_unique_id::*;
+/// # use std::sync::atomic::Ordering;
+/// unique_id_64! {
+///     /// Foo ID.
+///     pub struct FooId;
+///     atomic_option {
+///         /// Atomic `Option<FooId>`.
+///         pub struct AtomicOptionFooId;
+///     }
+/// }
+///
+/// let current = AtomicOptionFooId::none();
+/// assert!(current.load(Ordering::Relaxed).is_none());
+///
+/// let id = FooId::new_unique();
+/// assert!(current.compare_exchange(None, Some(id), Ordering::Relaxed, Ordering::Relaxed).is_ok());
+/// assert!(current.load(Ordering::Relaxed) == Some(id));
+/// ```
+///
+/// # Seed
+///
+/// IDs can also declare a `seed` that is mixed into the scramble function, so that different ID types generate
+/// different raw values from the same sequential numbers. The conversion between sequential and raw values
+/// round-trips for the same seed.
+///
+/// ```
+/// # use zng_unique_id::*;
+/// unique_id_64! {
+///     /// Bar ID.
+///     pub struct BarId;
+///     seed { 0x5eed }
+/// }
+///
+/// let id = BarId::from_sequential(1);
+/// assert_eq!(id.get(), splitmix64_seeded(1, 0x5eed));
+/// assert_eq!(id.sequential(), 1);
+/// ```
 #[macro_export]
 macro_rules! unique_id_64 {
-    ($(#[$attrs:meta])* $vis:vis struct $Type:ident $(< $T:ident $(:($($bounds:tt)+))? >)? $(: $ParentId:path)? ;) => {
+    (
+        $(#[$attrs:meta])* $vis:vis struct $Type:ident $(: $ParentId:path)? ;
+        $(seed { $seed:expr })?
+        atomic_option {
+            $(#[$a_attrs:meta])* $a_vis:vis struct $AtomicType:ident;
+        }
+    ) => {
+        $crate::unique_id_64! {
+            $(#[$attrs])* $vis struct $Type $(: $ParentId)? ;
+            $(seed { $seed })?
+        }
+        $crate::unique_id! {
+            atomic_option {
+                $(#[$a_attrs])* $a_vis struct $AtomicType($Type);
+            }
+            atomic {
+                std::sync::atomic::AtomicU64
+            }
+            literal {
+                u64
+            }
+        }
+    };
+    (
+        $(#[$attrs:meta])* $vis:vis struct $Type:ident $(< $T:ident $(:($($bounds:tt)+))? >)? $(: $ParentId:path)? ;
+        $(seed { $seed:expr })?
+    ) => {
         $crate::unique_id! {
             request {
                 $(#[$attrs])*
@@ -120,16 +221,19 @@ macro_rules! unique_id_64 {
                 std::sync::atomic::AtomicU64
             }
             next_id {
-                $crate::next_id64
+                $crate::next_id64_seeded
             }
             literal {
                 u64
             }
             to_hash {
-                $crate::splitmix64
+                $crate::splitmix64_seeded
             }
             to_sequential {
-                $crate::un_splitmix64
+                $crate::un_splitmix64_seeded
+            }
+            seed {
+                0 $(+ $seed)?
             }
         }
     };
@@ -173,6 +277,9 @@ macro_rules! unique_id {
         to_sequential {
             $to_sequential:path
         }
+        seed {
+            $seed:expr
+        }
     ) => {
 
         $(#[$attrs])*
@@ -206,6 +313,16 @@ macro_rules! unique_id {
                 Self::new_unique()
             }
         }
+        #[allow(clippy::unnecessary_cast)]
+        impl$(<$T $(: $($bounds)+)?>)? $crate::SequentialId for $Type $(<$T>)? {
+            fn sequential(self) -> u64 {
+                Self::sequential(self) as u64
+            }
+
+            fn from_sequential(num: u64) -> Self {
+                Self::from_sequential(num as $lit)
+            }
+        }
 
         #[allow(dead_code)]
         impl$(<$T $(: $($bounds)+)?>)? $Type $(<$T>)? {
@@ -219,6 +336,9 @@ macro_rules! unique_id {
                 next_id {
                     $next_id
                 }
+                seed {
+                    $seed
+                }
             }
 
             /// Retrieve the underlying value.
@@ -230,7 +350,7 @@ macro_rules! unique_id {
             ///
             /// If two IDs, `id0` and `id1` where generated by the same thread then `id0.sequential() < id1.sequential()`.
             pub fn sequential(self) -> $lit {
-                $to_sequential(self.0.get())
+                $to_sequential(self.0.get(), $seed)
             }
 
             /// Creates an ID from a raw value.
@@ -247,16 +367,114 @@ macro_rules! unique_id {
 
             /// Creates an ID from a [`sequential`] number.
             ///
-            /// # Safety
+            /// The value must have been provided by [`sequential`] otherwise the ID will not be unique.
             ///
-            /// The value must not be zero, panics if it is, the value must have been provided by [`sequential`] otherwise
-            /// the ID will not be unique.
+            /// # Panics
+            ///
+            /// Panics if the number scrambles to zero, that is when `num` is equal to the ID `seed`, or zero if no seed
+            /// is declared.
             ///
             /// [`sequential`]: Self::sequential
             pub fn from_sequential(num: $lit) -> Self {
                 use $non_zero as __non_zero;
 
-                Self(__non_zero::new($to_hash(num)).unwrap() $(, std::marker::PhantomData::<$T>)?)
+                Self(__non_zero::new($to_hash(num, $seed)).unwrap() $(, std::marker::PhantomData::<$T>)?)
+            }
+        }
+    };
+
+    (
+        atomic_option {
+            $(#[$attrs:meta])* $vis:vis struct $AtomicType:ident($Type:ident);
+        }
+        atomic {
+            $atomic:path
+        }
+        literal {
+            $lit:ident
+        }
+    ) => {
+        $(#[$attrs])*
+        $vis struct $AtomicType($atomic);
+
+        #[allow(dead_code)]
+        impl $AtomicType {
+            /// New with an initial `id`.
+            pub const fn new(id: Option<$Type>) -> Self {
+                use $atomic as __atomic;
+
+                Self(__atomic::new(match id {
+                    Some(id) => id.0.get(),
+                    None => 0,
+                }))
+            }
+
+            /// New with no ID.
+            pub const fn none() -> Self {
+                Self::new(None)
+            }
+
+            /// Loads the current ID.
+            pub fn load(&self, order: std::sync::atomic::Ordering) -> Option<$Type> {
+                Self::from_lit(self.0.load(order))
+            }
+
+            /// Stores the ID.
+            pub fn store(&self, id: Option<$Type>, order: std::sync::atomic::Ordering) {
+                self.0.store(Self::to_lit(id), order)
+            }
+
+            /// Stores the ID, returning the previous ID.
+            pub fn swap(&self, id: Option<$Type>, order: std::sync::atomic::Ordering) -> Option<$Type> {
+                Self::from_lit(self.0.swap(Self::to_lit(id), order))
+            }
+
+            /// Stores the `new` ID if the current ID is the same as `current`.
+            ///
+            /// Returns the previous ID, `Ok` if the value was updated.
+            pub fn compare_exchange(
+                &self,
+                current: Option<$Type>,
+                new: Option<$Type>,
+                success: std::sync::atomic::Ordering,
+                failure: std::sync::atomic::Ordering,
+            ) -> Result<Option<$Type>, Option<$Type>> {
+                self.0
+                    .compare_exchange(Self::to_lit(current), Self::to_lit(new), success, failure)
+                    .map(Self::from_lit)
+                    .map_err(Self::from_lit)
+            }
+
+            /// Takes the current ID, leaving `None`.
+            pub fn take(&self, order: std::sync::atomic::Ordering) -> Option<$Type> {
+                self.swap(None, order)
+            }
+
+            fn to_lit(id: Option<$Type>) -> $lit {
+                match id {
+                    Some(id) => id.get(),
+                    None => 0,
+                }
+            }
+
+            fn from_lit(raw: $lit) -> Option<$Type> {
+                if raw == 0 {
+                    None
+                } else {
+                    Some($Type::from_raw(raw))
+                }
+            }
+        }
+        impl Default for $AtomicType {
+            fn default() -> Self {
+                Self::none()
+            }
+        }
+        impl std::fmt::Debug for $AtomicType {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_tuple(stringify!($AtomicType))
+                    .field(&self.load(std::sync::atomic::Ordering::Relaxed).map(|id| id.get()))
+                    .finish()
             }
         }
     };
@@ -271,6 +489,9 @@ macro_rules! unique_id {
         next_id {
             $next_id:path
         }
+        seed {
+            $seed:expr
+        }
     ) => {
         /// Generates a new unique ID.
         pub fn new_unique() -> Self {
@@ -290,6 +511,9 @@ macro_rules! unique_id {
         next_id {
             $next_id:path
         }
+        seed {
+            $seed:expr
+        }
     ) => {
         /// Generates a new unique ID.
         pub fn new_unique() -> Self {
@@ -299,13 +523,17 @@ macro_rules! unique_id {
                 static NEXT: __atomic = __atomic::new(1);
             }
             let __ref = $crate::hot_static_ref!(NEXT);
-            Self($next_id(__ref) $(, std::marker::PhantomData::<$T>)?)
+            Self($next_id(__ref, $seed) $(, std::marker::PhantomData::<$T>)?)
         }
     };
 }
 
 #[doc(hidden)]
 pub fn next_id32(next: &'static AtomicU32) -> NonZeroU32 {
+    next_id32_seeded(next, 0)
+}
+#[doc(hidden)]
+pub fn next_id32_seeded(next: &'static AtomicU32, seed: u32) -> NonZeroU32 {
     loop {
         // the sequential next id is already in the variable.
         let id = next.fetch_add(1, Ordering::Relaxed);
@@ -313,7 +541,7 @@ pub fn next_id32(next: &'static AtomicU32) -> NonZeroU32 {
         if id == 0 {
             tracing::error!("id factory reached `u32::MAX`, will start reusing");
         } else {
-            let id = hash32(id);
+            let id = hash32_seeded(id, seed);
             if let Some(id) = NonZeroU32::new(id) {
                 return id;
             }
@@ -322,6 +550,10 @@ pub fn next_id32(next: &'static AtomicU32) -> NonZeroU32 {
 }
 #[doc(hidden)]
 pub fn next_id64(next: &'static AtomicU64) -> NonZeroU64 {
+    next_id64_seeded(next, 0)
+}
+#[doc(hidden)]
+pub fn next_id64_seeded(next: &'static AtomicU64, seed: u64) -> NonZeroU64 {
     loop {
         // the sequential next id is already in the variable.
         let id = next.fetch_add(1, Ordering::Relaxed);
@@ -330,7 +562,7 @@ pub fn next_id64(next: &'static AtomicU64) -> NonZeroU64 {
             tracing::error!("id factory reached `u64::MAX`, will start reusing");
         } else {
             // remove the sequential clustering.
-            let id = splitmix64(id);
+            let id = splitmix64_seeded(id, seed);
             if let Some(id) = NonZeroU64::new(id) {
                 return id;
             }
@@ -360,6 +592,19 @@ pub fn un_hash32(z: u32) -> u32 {
     n.0
 }
 
+/// Scramble `n` using a 32-bit integer hash mixed with the scrambled `seed`.
+///
+/// Different seeds produce different sequences from the same sequential numbers, the inverse is [`un_hash32_seeded`].
+/// The seed `0` produces the same sequence as the unseeded hash.
+pub fn hash32_seeded(n: u32, seed: u32) -> u32 {
+    hash32(n) ^ hash32(seed)
+}
+
+/// Inverse of [`hash32_seeded`] for the same `seed`.
+pub fn un_hash32_seeded(z: u32, seed: u32) -> u32 {
+    un_hash32(z ^ hash32(seed))
+}
+
 #[doc(hidden)]
 pub fn splitmix64(n: u64) -> u64 {
     use std::num::Wrapping as W;
@@ -382,6 +627,19 @@ pub fn un_splitmix64(z: u64) -> u64 {
     n.0
 }
 
+/// Scramble `n` using `splitmix64` mixed with the scrambled `seed`.
+///
+/// Different seeds produce different sequences from the same sequential numbers, the inverse is [`un_splitmix64_seeded`].
+/// The seed `0` produces the same sequence as the unseeded `splitmix64`.
+pub fn splitmix64_seeded(n: u64, seed: u64) -> u64 {
+    splitmix64(n) ^ splitmix64(seed)
+}
+
+/// Inverse of [`splitmix64_seeded`] for the same `seed`.
+pub fn un_splitmix64_seeded(z: u64, seed: u64) -> u64 {
+    un_splitmix64(z ^ splitmix64(seed))
+}
+
 /// Map specialized for unique IDs that are already a randomized hash.
 #[derive(Clone, Debug)]
 pub struct IdMap<K, V>(hashbrown::HashMap<K, V, BuildIdHasher>);
@@ -390,6 +648,25 @@ impl<K, V> IdMap<K, V> {
     pub const fn new() -> Self {
         Self(hashbrown::HashMap::with_hasher(BuildIdHasher))
     }
+
+    /// New with pre-allocated capacity for at least `capacity` entries.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(hashbrown::HashMap::with_capacity_and_hasher(capacity, BuildIdHasher))
+    }
+}
+impl<K: Eq + Hash, V> IdMap<K, V> {
+    /// Gets the value for the `key`, inserts the value returned by `init` if the key is not in the map.
+    pub fn get_or_insert_with(&mut self, key: K, init: impl FnOnce() -> V) -> &mut V {
+        self.0.entry(key).or_insert_with(init)
+    }
+
+    /// Gets the value for the `key`, inserts the default value if the key is not in the map.
+    pub fn get_or_default(&mut self, key: K) -> &mut V
+    where
+        V: Default,
+    {
+        self.0.entry(key).or_default()
+    }
 }
 impl<K, V> Default for IdMap<K, V> {
     fn default() -> Self {
@@ -475,6 +752,14 @@ impl<K: Eq + Hash + Send, V: Send> FromParallelIterator<(K, V)> for IdMap<K, V>
         Self(FromParallelIterator::from_par_iter(par_iter))
     }
 }
+impl<K: Eq + Hash + Send, V: Send> ParallelExtend<(K, V)> for IdMap<K, V> {
+    fn par_extend<I>(&mut self, par_iter: I)
+    where
+        I: IntoParallelIterator<Item = (K, V)>,
+    {
+        self.0.par_extend(par_iter)
+    }
+}
 
 /// Set specialized for unique IDs that are already a randomized hash.
 #[derive(Clone, Debug)]
@@ -484,6 +769,11 @@ impl<K> IdSet<K> {
     pub const fn new() -> Self {
         Self(hashbrown::HashSet::with_hasher(BuildIdHasher))
     }
+
+    /// New with pre-allocated capacity for at least `capacity` entries.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(hashbrown::HashSet::with_capacity_and_hasher(capacity, BuildIdHasher))
+    }
 }
 impl<K> Default for IdSet<K> {
     fn default() -> Self {
@@ -551,6 +841,14 @@ impl<K: Eq + Hash + Send> FromParallelIterator<K> for IdSet<K> {
         Self(FromParallelIterator::from_par_iter(par_iter))
     }
 }
+impl<K: Eq + Hash + Send> ParallelExtend<K> for IdSet<K> {
+    fn par_extend<I>(&mut self, par_iter: I)
+    where
+        I: IntoParallelIterator<Item = K>,
+    {
+        self.0.par_extend(par_iter)
+    }
+}
 impl<K: Eq + Hash> PartialEq for IdSet<K> {
     fn eq(&self, other: &Self) -> bool {
         self.0 == other.0
@@ -558,6 +856,246 @@ impl<K: Eq + Hash> PartialEq for IdSet<K> {
 }
 impl<K: Eq + Hash> Eq for IdSet<K> {}
 
+/// Set specialized for unique IDs that are generated close together.
+///
+/// The set is a bitset keyed on the [`SequentialId::sequential`] number of IDs, it only allocates one bit per number
+/// in the range between the smallest and largest IDs inserted, so it is far more compact than [`IdSet`] for dense ranges
+/// of IDs, like the IDs of widgets generated in sequence.
+///
+/// The bitset range only grows if the set stays dense enough, IDs that are too far from the range are stored in an
+/// [`IdSet`] fallback, so sparse IDs do not allocate proportional to the numeric gap between them, but they also don't
+/// benefit from the compact storage. Use [`IdSet`] directly for IDs that are mostly sparse.
+///
+/// # Examples
+///
+/// ```
+/// # use zng_unique_id::*;
+/// unique_id_64! {
+///     /// Foo ID.
+///     pub struct FooId;
+/// }
+///
+/// let ids: Vec<_> = (0..1000).map(|_| FooId::new_unique()).collect();
+///
+/// let mut set: SequentialIdSet<FooId> = ids.iter().copied().collect();
+/// assert_eq!(set.len(), 1000);
+/// assert!(set.contains(ids[500]));
+///
+/// assert!(set.remove(ids[500]));
+/// assert!(!set.contains(ids[500]));
+/// assert_eq!(set.iter().count(), 999);
+/// ```
+pub struct SequentialIdSet<K> {
+    // index of `words[0]`, in words.
+    start: u64,
+    words: Vec<u64>,
+    // IDs outside the `words` range that would grow it beyond the density limit.
+    sparse: IdSet<K>,
+    len: usize,
+}
+impl<K> SequentialIdSet<K> {
+    /// Minimum number of words the bitset can grow to, regardless of density (64 bits each, 8KB).
+    const MIN_SPAN: u64 = 1024;
+    /// Minimum density of the bitset after the `MIN_SPAN`, the bitset can grow to span this many words per ID in the set.
+    const SPAN_PER_ID: u64 = 2;
+
+    /// New `const` default.
+    pub const fn new() -> Self {
+        Self {
+            start: 0,
+            words: vec![],
+            sparse: IdSet::new(),
+            len: 0,
+        }
+    }
+
+    /// Number of IDs in the set.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// If the set has no IDs.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Remove all IDs and release the allocated memory.
+    pub fn clear(&mut self) {
+        self.start = 0;
+        self.words = vec![];
+        self.sparse = IdSet::new();
+        self.len = 0;
+    }
+
+    /// Number of words the bitset would span to include `word`, or `None` if it is already included.
+    fn span_with(&self, word: u64) -> Option<u64> {
+        if word < self.start {
+            Some(self.start - word + self.words.len() as u64)
+        } else if word - self.start >= self.words.len() as u64 {
+            Some(word - self.start + 1)
+        } else {
+            None
+        }
+    }
+}
+impl<K: SequentialId> SequentialIdSet<K> {
+    /// Returns `true` if the set contains the `id`.
+    pub fn contains(&self, id: K) -> bool {
+        let (word, bit) = Self::position(id);
+        match word.checked_sub(self.start).and_then(|i| self.words.get(i as usize)) {
+            Some(w) => w & bit != 0,
+            None => self.sparse.contains(&id),
+        }
+    }
+
+    /// Insert the `id`, returns `true` if it was not already in the set.
+    pub fn insert(&mut self, id: K) -> bool {
+        let (word, bit) = Self::position(id);
+        if self.words.is_empty() {
+            self.start = word;
+            self.words.push(0);
+            self.migrate_sparse();
+        } else if let Some(span) = self.span_with(word) {
+            if span > Self::MIN_SPAN.max(self.len as u64 * Self::SPAN_PER_ID) {
+                let inserted = self.sparse.insert(id);
+                if inserted {
+                    self.len += 1;
+                }
+                return inserted;
+            }
+
+            if word < self.start {
+                let mut words = vec![0; (self.start - word) as usize];
+                words.append(&mut self.words);
+                self.words = words;
+                self.start = word;
+            } else {
+                self.words.resize(span as usize, 0);
+            }
+
+            self.migrate_sparse();
+        }
+
+        let w = &mut self.words[(word - self.start) as usize];
+        let inserted = *w & bit == 0;
+        *w |= bit;
+        if inserted {
+            self.len += 1;
+        }
+        inserted
+    }
+
+    /// Remove the `id`, returns `true` if it was in the set.
+    pub fn remove(&mut self, id: K) -> bool {
+        let (word, bit) = Self::position(id);
+        let removed = match word.checked_sub(self.start).and_then(|i| self.words.get_mut(i as usize)) {
+            Some(w) => {
+                let removed = *w & bit != 0;
+                *w &= !bit;
+                removed
+            }
+            None => self.sparse.remove(&id),
+        };
+        if removed {
+            self.len -= 1;
+            if self.len == 0 {
+                self.clear();
+            }
+        }
+        removed
+    }
+
+    /// Iterate over the IDs in the set.
+    ///
+    /// The IDs in the bitset range are visited first in sequential order, followed by the sparse IDs in any order.
+    pub fn iter(&self) -> impl Iterator<Item = K> + '_ {
+        let start = self.start;
+        self.words
+            .iter()
+            .enumerate()
+            .flat_map(move |(i, &w)| {
+                let base = (start + i as u64) * 64;
+                (0..64u64)
+                    .filter(move |b| w & (1 << b) != 0)
+                    .map(move |b| K::from_sequential(base + b))
+            })
+            .chain(self.sparse.iter().copied())
+    }
+
+    /// Move sparse IDs that are now in the bitset range.
+    fn migrate_sparse(&mut self) {
+        if self.sparse.is_empty() {
+            return;
+        }
+        let (start, words) = (self.start, &mut self.words);
+        self.sparse.retain(|&id| {
+            let (word, bit) = Self::position(id);
+            match word.checked_sub(start).and_then(|i| words.get_mut(i as usize)) {
+                Some(w) => {
+                    *w |= bit;
+                    false
+                }
+                None => true,
+            }
+        });
+    }
+
+    fn position(id: K) -> (u64, u64) {
+        let n = id.sequential();
+        (n / 64, 1 << (n % 64))
+    }
+}
+impl<K: Clone> Clone for SequentialIdSet<K> {
+    fn clone(&self) -> Self {
+        Self {
+            start: self.start,
+            words: self.words.clone(),
+            sparse: self.sparse.clone(),
+            len: self.len,
+        }
+    }
+}
+impl<K> Default for SequentialIdSet<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<K: SequentialId + std::fmt::Debug> std::fmt::Debug for SequentialIdSet<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+impl<K: SequentialId> PartialEq for SequentialIdSet<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().all(|id| other.contains(id))
+    }
+}
+impl<K: SequentialId> Eq for SequentialIdSet<K> {}
+impl<K: SequentialId> FromIterator<K> for SequentialIdSet<K> {
+    fn from_iter<T: IntoIterator<Item = K>>(iter: T) -> Self {
+        let mut r = Self::new();
+        r.extend(iter);
+        r
+    }
+}
+impl<K: SequentialId> Extend<K> for SequentialIdSet<K> {
+    fn extend<T: IntoIterator<Item = K>>(&mut self, iter: T) {
+        for id in iter {
+            self.insert(id);
+        }
+    }
+}
+impl<K: SequentialId> From<IdSet<K>> for SequentialIdSet<K> {
+    fn from(set: IdSet<K>) -> Self {
+        set.into_iter().collect()
+    }
+}
+impl<K: SequentialId> From<SequentialIdSet<K>> for IdSet<K> {
+    fn from(set: SequentialIdSet<K>) -> Self {
+        set.iter().collect()
+    }
+}
+
 /// Entry in [`IdMap`].
 pub type IdEntry<'a, K, V> = hashbrown::hash_map::Entry<'a, K, V, BuildIdHasher>;
 
@@ -609,6 +1147,23 @@ pub trait UniqueId: Clone + Copy + PartialEq + Eq + Hash {
     fn new_unique() -> Self;
 }
 
+/// Trait implemented for all generated unique ID types, provides access to the sequential count number.
+pub trait SequentialId: UniqueId {
+    /// Un-scramble the ID to get the original sequential count number.
+    fn sequential(self) -> u64;
+
+    /// Creates an ID from a [`sequential`] number.
+    ///
+    /// The value must have been provided by [`sequential`] otherwise the ID will not be unique.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number scrambles to zero, that is when `num` is equal to the ID seed, or zero if the ID has no seed.
+    ///
+    /// [`sequential`]: Self::sequential
+    fn from_sequential(num: u64) -> Self;
+}
+
 /// Declares a static unique ID that is lazy inited.
 ///
 /// Dereferencing this static generates the ID and caches it.
@@ -642,3 +1197,135 @@ macro_rules! static_id {
         )+
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    unique_id_64! {
+        struct TestId;
+    }
+
+    #[test]
+    fn hash32_seeded_round_trip() {
+        for seed in [0, 1, 0x5eed, u32::MAX] {
+            for n in (0..10_000).chain([u32::MAX - 1, u32::MAX]) {
+                assert_eq!(n, un_hash32_seeded(hash32_seeded(n, seed), seed));
+            }
+        }
+    }
+
+    #[test]
+    fn splitmix64_seeded_round_trip() {
+        for seed in [0, 1, 0x5eed, u64::MAX] {
+            for n in (0..10_000).chain([u64::MAX - 1, u64::MAX]) {
+                assert_eq!(n, un_splitmix64_seeded(splitmix64_seeded(n, seed), seed));
+            }
+        }
+    }
+
+    unique_id_64! {
+        struct SeededTestId;
+        seed { 0x5eed }
+    }
+
+    #[test]
+    fn from_sequential_seeded_zero() {
+        assert_eq!(0, SeededTestId::from_sequential(0).sequential());
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_sequential_seeded_seed() {
+        let _ = SeededTestId::from_sequential(0x5eed);
+    }
+
+    #[test]
+    fn hash32_seeded_disjoint() {
+        let unseeded: std::collections::HashSet<_> = (1..10_000).map(hash32).collect();
+        for n in 1..10_000 {
+            assert!(!unseeded.contains(&hash32_seeded(n, 0x5eed)), "seeded id {n} collides");
+        }
+    }
+
+    #[test]
+    fn splitmix64_seeded_disjoint() {
+        let unseeded: std::collections::HashSet<_> = (1..100_000).map(splitmix64).collect();
+        for n in 1..100_000 {
+            assert!(!unseeded.contains(&splitmix64_seeded(n, 0x5eed)), "seeded id {n} collides");
+        }
+    }
+
+    #[test]
+    fn sequential_id_set_insert_remove() {
+        let ids: Vec<_> = (0..1000).map(|_| TestId::new_unique()).collect();
+        let mut set = SequentialIdSet::new();
+        for &id in &ids {
+            assert!(set.insert(id));
+        }
+        assert!(!set.insert(ids[10]));
+        assert_eq!(set.len(), 1000);
+        assert!(set.sparse.is_empty());
+        assert!(set.words.len() <= 17);
+
+        assert!(ids.iter().all(|&id| set.contains(id)));
+        assert!(!set.contains(TestId::new_unique()));
+
+        assert!(set.remove(ids[10]));
+        assert!(!set.remove(ids[10]));
+        assert!(!set.contains(ids[10]));
+        assert_eq!(set.len(), 999);
+
+        for &id in &ids {
+            set.remove(id);
+        }
+        assert!(set.is_empty());
+        assert!(set.words.is_empty());
+    }
+
+    #[test]
+    fn sequential_id_set_sparse() {
+        let ids = [
+            TestId::from_sequential(1),
+            TestId::from_sequential(1 << 40),
+            TestId::from_sequential(u64::MAX / 2),
+        ];
+        let mut set: SequentialIdSet<_> = ids.iter().copied().collect();
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.words.len(), 1);
+        assert_eq!(set.sparse.len(), 2);
+        assert!(ids.iter().all(|&id| set.contains(id)));
+        assert_eq!(set.iter().count(), 3);
+
+        assert!(set.remove(ids[1]));
+        assert!(!set.contains(ids[1]));
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn sequential_id_set_sparse_migrates() {
+        let mut set = SequentialIdSet::new();
+        set.insert(TestId::from_sequential(1));
+        let far = TestId::from_sequential(64 * 1500);
+        set.insert(far);
+        assert_eq!(set.sparse.len(), 1);
+
+        for k in 1..1600 {
+            set.insert(TestId::from_sequential(64 * k));
+        }
+        assert!(set.sparse.is_empty());
+        assert_eq!(set.len(), 1600);
+        assert!(set.contains(far));
+        assert_eq!(set.iter().count(), 1600);
+    }
+
+    #[test]
+    fn sequential_id_set_id_set_round_trip() {
+        let mut set: SequentialIdSet<_> = (0..100).map(|_| TestId::new_unique()).collect();
+        set.insert(TestId::from_sequential(1 << 40));
+
+        let id_set = IdSet::from(set.clone());
+        assert_eq!(id_set.len(), 101);
+        assert!(SequentialIdSet::from(id_set) == set);
+    }
+}
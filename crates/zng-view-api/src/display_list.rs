@@ -10,7 +10,7 @@ use serde::{Deserialize, Serialize};
 use crate::{
     api_extension::{ApiExtensionId, ApiExtensionPayload},
     font::{FontId, GlyphInstance, GlyphOptions},
-    image::ImageTextureId,
+    image::{ExternalImageId, ImageTextureId},
     window::FrameId,
     AlphaType, BorderSide, ExtendMode, GradientStop, ImageRendering, LineOrientation, LineStyle, MixBlendMode, ReferenceFrameId,
     RepeatMode, TransformStyle,
@@ -164,6 +164,40 @@ impl DisplayListBuilder {
         self.list.push(DisplayItem::PopReferenceFrame);
     }
 
+    /// Start a new sticky spatial context, must be paired with a call to [`pop_sticky_frame`].
+    ///
+    /// The `frame_rect` is the sticky content bounds in the parent space. The `margins` define the distance to each edge of the
+    /// viewport the content pins to, `None` sides do not pin. The offset bounds define the minimum and maximum offset the content can
+    /// be moved to stay pinned in each axis.
+    ///
+    /// [`pop_sticky_frame`]: Self::pop_sticky_frame
+    pub fn push_sticky_frame(
+        &mut self,
+        key: ReferenceFrameId,
+        frame_rect: PxRect,
+        margins: euclid::SideOffsets2D<Option<Px>, Px>,
+        vertical_offset_bounds: (Px, Px),
+        horizontal_offset_bounds: (Px, Px),
+    ) {
+        self.space_len += 1;
+        self.list.push(DisplayItem::PushStickyFrame {
+            id: key,
+            frame_rect,
+            margins,
+            vertical_offset_bounds,
+            horizontal_offset_bounds,
+        });
+    }
+
+    /// Finish the sticky spatial context started by a call to [`push_sticky_frame`].
+    ///
+    /// [`push_sticky_frame`]: Self::push_sticky_frame
+    pub fn pop_sticky_frame(&mut self) {
+        debug_assert!(self.space_len > 1);
+        self.space_len -= 1;
+        self.list.push(DisplayItem::PopStickyFrame);
+    }
+
     /// Start a new filters context or extend 3D space, must be paired with a call to [`pop_stacking_context`].
     ///
     /// Note that `transform_style` is coerced to `Flat` if any filter is also set.
@@ -237,6 +271,25 @@ impl DisplayListBuilder {
         self.list.push(DisplayItem::PopMask);
     }
 
+    /// Push a shadow that will be rendered for all text items pushed until a paired call to [`pop_all_shadows`].
+    ///
+    /// [`pop_all_shadows`]: Self::pop_all_shadows
+    pub fn push_shadow(&mut self, offset: euclid::Vector2D<f32, Px>, color: Rgba, blur_radius: f32) {
+        self.list.push(DisplayItem::PushShadow {
+            offset,
+            color,
+            blur_radius,
+        })
+    }
+
+    /// Pop all shadows previously pushed by calls to [`push_shadow`]. Items pushed after this call do not
+    /// render a shadow.
+    ///
+    /// [`push_shadow`]: Self::push_shadow
+    pub fn pop_all_shadows(&mut self) {
+        self.list.push(DisplayItem::PopAllShadows);
+    }
+
     /// Push a normal border.
     #[allow(clippy::too_many_arguments)]
     pub fn push_border(
@@ -319,6 +372,13 @@ impl DisplayListBuilder {
         })
     }
 
+    /// Push an external image or texture.
+    ///
+    /// The image is stretched to fill the `clip_rect`, composited with the current transform and clip.
+    pub fn push_external_image(&mut self, clip_rect: PxRect, image_id: ExternalImageId) {
+        self.list.push(DisplayItem::ExternalImage { clip_rect, image_id })
+    }
+
     /// Push a color rectangle.
     pub fn push_color(&mut self, clip_rect: PxRect, color: FrameValue<Rgba>) {
         self.list.push(DisplayItem::Color { clip_rect, color })
@@ -798,6 +858,15 @@ pub enum DisplayItem {
     },
     PopReferenceFrame,
 
+    PushStickyFrame {
+        id: ReferenceFrameId,
+        frame_rect: PxRect,
+        margins: euclid::SideOffsets2D<Option<Px>, Px>,
+        vertical_offset_bounds: (Px, Px),
+        horizontal_offset_bounds: (Px, Px),
+    },
+    PopStickyFrame,
+
     PushStackingContext {
         transform_style: TransformStyle,
         blend_mode: MixBlendMode,
@@ -821,6 +890,13 @@ pub enum DisplayItem {
     },
     PopMask,
 
+    PushShadow {
+        offset: euclid::Vector2D<f32, Px>,
+        color: Rgba,
+        blur_radius: f32,
+    },
+    PopAllShadows,
+
     Border {
         bounds: PxRect,
         widths: PxSideOffsets,
@@ -854,6 +930,11 @@ pub enum DisplayItem {
         tile_spacing: PxSize,
     },
 
+    ExternalImage {
+        clip_rect: PxRect,
+        image_id: ExternalImageId,
+    },
+
     Color {
         clip_rect: PxRect,
         color: FrameValue<Rgba>,
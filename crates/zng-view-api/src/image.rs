@@ -1,6 +1,6 @@
 //! Image types.
 
-use std::fmt;
+use std::{fmt, time::Duration};
 
 use serde::{Deserialize, Serialize};
 use zng_txt::Txt;
@@ -18,8 +18,21 @@ crate::declare_id! {
     ///
     /// The View Process defines the ID.
     pub struct ImageTextureId(_);
+
+    /// Id of an image encode request.
+    ///
+    /// The View Process defines the ID.
+    pub struct ImageEncodeId(_);
 }
 
+/// Id of an external image or texture registered in a renderer by a view-process extension.
+///
+/// The view-process extension defines the ID, in the default view-process this is the
+/// `ExternalImageId` returned by `ExternalImages::register_texture` and `register_image`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ExternalImageId(pub u64);
+
 /// Defines how the A8 image mask pixels are to be derived from a source mask image.
 #[derive(Debug, Copy, Clone, Serialize, PartialEq, Eq, Hash, Deserialize, Default)]
 pub enum ImageMaskMode {
@@ -69,6 +82,28 @@ pub struct ImageRequest<D> {
     pub mask: Option<ImageMaskMode>,
 }
 
+/// Options for [`Api::encode_image_opts`].
+///
+/// All options are `None` by default, meaning the encoder defaults are used. Options that don't apply to the
+/// requested format are ignored.
+///
+/// [`Api::encode_image_opts`]: crate::Api::encode_image_opts
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct EncodeOptions {
+    /// Lossy compression quality, from `1` (smallest) to `100` (best).
+    ///
+    /// Used by JPEG.
+    pub quality: Option<u8>,
+    /// Lossless compression level, from `0` (fastest) to `9` (smallest).
+    ///
+    /// Used by PNG, the level is approximated to the levels supported by the encoder.
+    pub compression: Option<u8>,
+    /// Number of times an animation repeats after the first play, `None` repeats forever.
+    ///
+    /// Used by GIF.
+    pub loop_count: Option<u16>,
+}
+
 /// Defines how an image is downscaled after decoding.
 ///
 /// The image aspect ratio is preserved in both modes, the image is not upscaled, if it already fits the size
@@ -182,6 +217,71 @@ pub enum ImageDataFormat {
     /// on the beginning of the bytes buffer.
     Unknown,
 }
+impl ImageDataFormat {
+    /// Get the format that matches the file extension, or [`Unknown`] if the extension is not a known image format.
+    ///
+    /// The extension is case insensitive and can have a leading dot. The known formats are common image formats, not
+    /// all of them are decoded by every view-process implementation or build, see [`Api::image_decoders`] for the formats
+    /// actually supported by the view-process.
+    ///
+    /// The [`Unknown`] format causes the decoder to be selected using the "magic number" on the beginning of the data.
+    ///
+    /// [`Unknown`]: ImageDataFormat::Unknown
+    /// [`Api::image_decoders`]: crate::Api::image_decoders
+    pub fn from_extension(ext: &str) -> Self {
+        let ext = ext.trim().trim_start_matches('.').to_ascii_lowercase();
+        if KNOWN_FORMATS.iter().any(|(e, _)| *e == ext) {
+            ImageDataFormat::FileExtension(ext.into())
+        } else {
+            ImageDataFormat::Unknown
+        }
+    }
+
+    /// Get the format that matches the MIME type, or [`Unknown`] if the type is not a known image format.
+    ///
+    /// The MIME type is case insensitive and parameters are ignored. Matched types are converted to the equivalent
+    /// [`FileExtension`], see [`from_extension`] for more details.
+    ///
+    /// [`Unknown`]: ImageDataFormat::Unknown
+    /// [`FileExtension`]: ImageDataFormat::FileExtension
+    /// [`from_extension`]: ImageDataFormat::from_extension
+    pub fn from_mime(mime: &str) -> Self {
+        let mime = mime.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+        match KNOWN_FORMATS.iter().find(|(_, m)| m.contains(&mime.as_str())) {
+            Some((ext, _)) => ImageDataFormat::FileExtension(Txt::from_static(ext)),
+            None => ImageDataFormat::Unknown,
+        }
+    }
+}
+/// File extensions and MIME types of common image formats.
+///
+/// This is not the list of formats decoded by the view-process, that depends on the implementation and build features,
+/// it is only used to recognize image formats. Formats with multiple extensions only list the MIME types in the first entry, that is the extension matched by MIME.
+const KNOWN_FORMATS: &[(&str, &[&str])] = &[
+    ("png", &["image/png", "image/apng"]),
+    ("jpg", &["image/jpeg", "image/jpg", "image/pjpeg"]),
+    ("jpeg", &[]),
+    ("jfif", &[]),
+    ("webp", &["image/webp"]),
+    ("avif", &["image/avif"]),
+    ("gif", &["image/gif"]),
+    ("ico", &["image/x-icon", "image/vnd.microsoft.icon"]),
+    ("bmp", &["image/bmp", "image/x-bmp", "image/x-ms-bmp"]),
+    ("exr", &["image/x-exr", "image/aces"]),
+    ("hdr", &["image/vnd.radiance", "image/x-hdr"]),
+    ("pnm", &["image/x-portable-anymap"]),
+    ("pbm", &["image/x-portable-bitmap"]),
+    ("pgm", &["image/x-portable-graymap"]),
+    ("ppm", &["image/x-portable-pixmap"]),
+    ("pam", &[]),
+    ("qoi", &["image/qoi", "image/x-qoi"]),
+    ("ff", &["image/x-farbfeld"]),
+    ("farbfeld", &[]),
+    ("dds", &["image/vnd-ms.dds", "image/vnd.ms-dds"]),
+    ("tga", &["image/x-targa", "image/x-tga"]),
+    ("tif", &["image/tiff"]),
+    ("tiff", &[]),
+];
 impl From<Txt> for ImageDataFormat {
     fn from(ext_or_mime: Txt) -> Self {
         if ext_or_mime.contains('/') {
@@ -257,6 +357,15 @@ pub struct ImageLoadedData {
     pub is_mask: bool,
     /// Reference to the BGRA8 pre-multiplied image pixels or the A8 pixels if `is_mask`.
     pub pixels: IpcBytes,
+    /// Display duration of this image if it is the first frame of an animation.
+    ///
+    /// Is zero for still images.
+    pub frame_delay: Duration,
+    /// Other frames of an animated image.
+    ///
+    /// Is empty for still images. Each frame is a full image with the same size and format of this image,
+    /// loaded in the view-process cache with its own ID.
+    pub frames: Vec<ImageFrameData>,
 }
 impl fmt::Debug for ImageLoadedData {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -267,6 +376,33 @@ impl fmt::Debug for ImageLoadedData {
             .field("is_opaque", &self.is_opaque)
             .field("is_mask", &self.is_mask)
             .field("pixels", &format_args!("<{} bytes shared memory>", self.pixels.len()))
+            .field("frame_delay", &self.frame_delay)
+            .field("frames", &self.frames)
+            .finish()
+    }
+}
+
+/// Represents a frame of an animated image.
+///
+/// See [`ImageLoadedData::frames`].
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImageFrameData {
+    /// Frame image ID.
+    pub id: ImageId,
+    /// Display duration of the frame.
+    pub delay: Duration,
+    /// If all pixels have an alpha value of 255.
+    pub is_opaque: bool,
+    /// Reference to the BGRA8 pre-multiplied frame pixels.
+    pub pixels: IpcBytes,
+}
+impl fmt::Debug for ImageFrameData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ImageFrameData")
+            .field("id", &self.id)
+            .field("delay", &self.delay)
+            .field("is_opaque", &self.is_opaque)
+            .field("pixels", &format_args!("<{} bytes shared memory>", self.pixels.len()))
             .finish()
     }
 }
@@ -321,3 +457,35 @@ zng_var::impl_from_and_into_var! {
     fn from(xy: f32) -> ImagePpi;
     fn from(xy: (f32, f32)) -> ImagePpi;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_from_extension() {
+        assert_eq!(
+            ImageDataFormat::FileExtension("png".into()),
+            ImageDataFormat::from_extension(".PNG")
+        );
+        assert_eq!(
+            ImageDataFormat::FileExtension("jpeg".into()),
+            ImageDataFormat::from_extension("jpeg")
+        );
+        assert_eq!(ImageDataFormat::Unknown, ImageDataFormat::from_extension("txt"));
+        assert_eq!(ImageDataFormat::Unknown, ImageDataFormat::from_extension(""));
+    }
+
+    #[test]
+    fn format_from_mime() {
+        assert_eq!(
+            ImageDataFormat::FileExtension("jpg".into()),
+            ImageDataFormat::from_mime("image/JPEG")
+        );
+        assert_eq!(
+            ImageDataFormat::FileExtension("ico".into()),
+            ImageDataFormat::from_mime("image/x-icon; charset=binary")
+        );
+        assert_eq!(ImageDataFormat::Unknown, ImageDataFormat::from_mime("text/plain"));
+    }
+}
@@ -27,6 +27,9 @@ use serde::{Deserialize, Serialize};
 
 /// The *App Process* and *View Process* must be build using the same exact version and this is
 /// validated during run-time, causing a panic if the versions don't match.
+///
+/// Note that even patch versions are not compatible, the IPC protocol serializes requests and events by position,
+/// so any new API method, event or type field changes the protocol.
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub mod access;
@@ -300,14 +303,38 @@ declare_api! {
     pub fn set_visible(&mut self, id: WindowId, visible: bool);
 
     /// Set if the window is "top-most".
+    ///
+    /// This is the same as [`set_window_level`] with `AlwaysOnTop` or `Normal`.
+    ///
+    /// [`set_window_level`]: Api::set_window_level
     pub fn set_always_on_top(&mut self, id: WindowId, always_on_top: bool);
 
+    /// Set the window z-order level.
+    pub fn set_window_level(&mut self, id: WindowId, level: window::WindowLevel);
+
+    /// Set the color scheme of the window chrome, title bar and system controls.
+    ///
+    /// If set to `None` the system color scheme is used.
+    pub fn set_preferred_theme(&mut self, id: WindowId, theme: Option<config::ColorScheme>);
+
     /// Set if the user can drag-move the window when it is in `Normal` mode.
     pub fn set_movable(&mut self, id: WindowId, movable: bool);
 
     /// Set if the user can resize the window when it is in `Normal` mode.
     pub fn set_resizable(&mut self, id: WindowId, resizable: bool);
 
+    /// Set the width/height ratio the window maintains when it is resized by the user in `Normal` mode.
+    ///
+    /// When set the opposite dimension of a resize is adjusted to maintain the ratio before [`Event::WindowChanged`]
+    /// is send. Set to `None` to remove the constraint.
+    pub fn set_aspect_ratio(&mut self, id: WindowId, ratio: Option<f32>);
+
+    /// Set if the window restore rectangle is clamped to the current monitor when applied in `Normal` mode.
+    ///
+    /// When enabled a restore size larger than the monitor is reduced and the position is adjusted so that the
+    /// window is fully inside the monitor. The applied size and position are notified by [`Event::WindowChanged`].
+    pub fn set_clamp_to_monitor(&mut self, id: WindowId, clamp: bool);
+
     /// Set the window taskbar icon visibility.
     pub fn set_taskbar_visible(&mut self, id: WindowId, visible: bool);
 
@@ -323,6 +350,12 @@ declare_api! {
     /// Set the window icon, the icon image must be loaded.
     pub fn set_icon(&mut self, id: WindowId, icon: Option<ImageId>);
 
+    /// Set the window icon from the last rendered frame of the headless `surface`.
+    ///
+    /// This avoids an encode/decode round trip for icons rendered at runtime. The window icon is unset if
+    /// the surface is not found or has not rendered a frame yet.
+    pub fn set_window_icon_from_surface(&mut self, id: WindowId, surface: WindowId);
+
     /// Set the window cursor icon and visibility.
     pub fn set_cursor(&mut self, id: WindowId, cursor: Option<window::CursorIcon>);
 
@@ -384,19 +417,33 @@ declare_api! {
     /// [`add_image`]: Api::add_image
     pub fn add_image_pro(&mut self, request: ImageRequest<IpcBytesReceiver>) -> ImageId;
 
-    /// Remove an image from cache.
+    /// Release a reference to the image, the image is removed from cache when no references remain.
     ///
-    /// Note that if the image is in use in a renderer it will remain in memory until [`delete_image_use`] is
-    /// called or the renderer is deinited by closing the window.
+    /// The image is created with one reference, each renderer texture created by [`use_image`] or
+    /// [`update_image_use`] holds another reference that is released by [`delete_image_use`] or when
+    /// the renderer is deinited by closing the window.
     ///
+    /// [`use_image`]: Api::use_image
+    /// [`update_image_use`]: Api::update_image_use
     /// [`delete_image_use`]: Api::delete_image_use
     pub fn forget_image(&mut self, id: ImageId);
 
+    /// Gets the number of references to the image, or `0` if the image is not loaded.
+    ///
+    /// This is for debugging, see [`forget_image`] for more details about image references.
+    ///
+    /// [`forget_image`]: Api::forget_image
+    pub fn image_ref_count(&mut self, id: ImageId) -> usize;
+
     /// Add an image resource to the window renderer.
     ///
     /// Returns the new image texture ID. If the `image_id` is not loaded returns the [`INVALID`] image ID.
     ///
+    /// The renderer holds a reference to the image while the texture is in use, so the image is not
+    /// removed by [`forget_image`] until the texture is deleted.
+    ///
     /// [`INVALID`]: ImageTextureId::INVALID
+    /// [`forget_image`]: Api::forget_image
     pub fn use_image(&mut self, id: WindowId, image_id: ImageId) -> ImageTextureId;
 
     /// Replace the image resource in the window renderer.
@@ -422,6 +469,19 @@ declare_api! {
     /// Each string is the lower-case file extension.
     pub fn image_encoders(&mut self) -> Vec<Txt>;
 
+    /// Restrict the image decoders that can be used by [`add_image`] and [`add_image_pro`].
+    ///
+    /// Each string is the file extension of an enabled decoder, all [`image_decoders`] are enabled by default.
+    /// Images with a format that is not enabled fail to load with an [`Event::ImageLoadError`].
+    ///
+    /// [`add_image`]: Api::add_image
+    /// [`add_image_pro`]: Api::add_image_pro
+    /// [`image_decoders`]: Api::image_decoders
+    pub fn set_enabled_decoders(&mut self, formats: Vec<Txt>);
+
+    /// Set the renderers GPU memory usage thresholds of [`Event::MemoryPressure`].
+    pub fn set_memory_pressure_thresholds(&mut self, thresholds: MemoryPressureThresholds);
+
     /// Encode the image into the `format`.
     ///
     /// The format must be one of the values returned by [`image_encoders`].
@@ -432,6 +492,27 @@ declare_api! {
     /// [`image_encoders`]: Api::image_encoders
     pub fn encode_image(&mut self, id: ImageId, format: Txt);
 
+    /// Encode the image into the `format` with custom encoder options.
+    ///
+    /// Same as [`encode_image`], but the encoder quality, compression and animation loop count can be configured.
+    /// Returns the request ID that identifies the result events.
+    ///
+    /// [`encode_image`]: Api::encode_image
+    pub fn encode_image_opts(&mut self, id: ImageId, format: Txt, opts: image::EncodeOptions) -> image::ImageEncodeId;
+
+    /// Encode the image into the `format`, the encoded data is streamed in the returned receiver.
+    ///
+    /// The format must be one of the values returned by [`image_encoders`].
+    ///
+    /// Returns immediately. Each package received is the continuation of the previous package, an empty
+    /// package indicates finish. If encoding fails the error is logged and the channel disconnects without
+    /// the empty package.
+    ///
+    /// Note that some formats require seeking the output and are fully encoded before streaming.
+    ///
+    /// [`image_encoders`]: Api::image_encoders
+    pub fn encode_image_streaming(&mut self, id: ImageId, format: Txt) -> IpcBytesReceiver;
+
     /// Add a raw font resource to the window renderer.
     ///
     /// Returns the new font key.
@@ -477,6 +558,19 @@ declare_api! {
     /// Returns `0` if the window is not found.
     pub fn frame_image_rect(&mut self, id: WindowId, rect: PxRect, mask: Option<ImageMaskMode>) -> ImageId;
 
+    /// Get the graphics adapter and driver info of the window or surface renderer.
+    ///
+    /// Returns the default value if the window is not found.
+    pub fn renderer_info(&mut self, id: WindowId) -> window::RendererInfo;
+
+    /// Compute a hash of the pixels of the current rendered frame.
+    ///
+    /// The hash is deterministic, the same pixels always generate the same hash in any platform, this
+    /// can be used to detect render regressions without transferring the full frame image.
+    ///
+    /// Returns `(FrameId::INVALID, 0)` if the window is not found or has not rendered a frame yet.
+    pub fn frame_image_hash(&mut self, id: WindowId) -> (window::FrameId, u64);
+
     /// Set the video mode used when the window is in exclusive fullscreen.
     pub fn set_video_mode(&mut self, id: WindowId, mode: window::VideoMode);
 
@@ -486,6 +580,12 @@ declare_api! {
     /// Update the current frame and re-render it.
     pub fn render_update(&mut self, id: WindowId, frame: window::FrameUpdateRequest);
 
+    /// Request a one-shot [`Event::FramePresented`] notification for when the `frame` is presented on screen.
+    ///
+    /// If the `frame` or a newer frame was already presented the event is send immediately. Headless surfaces
+    /// do not present, the event is send when the frame finishes rendering.
+    pub fn request_frame_presented(&mut self, id: WindowId, frame: window::FrameId);
+
     /// Update the window's accessibility info tree.
     pub fn access_update(&mut self, id: WindowId, update: access::AccessTreeUpdate);
 
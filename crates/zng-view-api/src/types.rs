@@ -5,12 +5,12 @@ use crate::{
     api_extension::{ApiExtensionId, ApiExtensionPayload, ApiExtensions},
     config::{AnimationsConfig, ColorsConfig, FontAntiAliasing, KeyRepeatConfig, LocaleConfig, MultiClickConfig, TouchConfig},
     dialog::{DialogId, FileDialogResponse, MsgDialogResponse},
-    image::{ImageId, ImageLoadedData, ImagePpi},
+    image::{ImageEncodeId, ImageId, ImageLoadedData, ImagePpi},
     ipc::IpcBytes,
     keyboard::{Key, KeyCode, KeyLocation, KeyState},
     mouse::{ButtonId, ButtonState, MouseButton, MouseScrollDelta},
     touch::{TouchPhase, TouchUpdate},
-    window::{EventFrameRendered, FrameId, HeadlessOpenData, MonitorId, MonitorInfo, WindowChanged, WindowId, WindowOpenData},
+    window::{EventFrameRendered, FrameId, HeadlessOpenData, MonitorId, MonitorInfo, VideoMode, WindowChanged, WindowId, WindowOpenData},
 };
 use serde::{Deserialize, Serialize};
 use std::{fmt, path::PathBuf};
@@ -188,6 +188,20 @@ pub enum Event {
     /// `EventsCleared` is not send after this event.
     FrameRendered(EventFrameRendered),
 
+    /// A frame requested by [`Api::request_frame_presented`] was presented on screen.
+    ///
+    /// Unlike [`FrameRendered`] that is send when the frame finishes rendering, this event is send after the frame is
+    /// composited and presented.
+    ///
+    /// [`Api::request_frame_presented`]: crate::Api::request_frame_presented
+    /// [`FrameRendered`]: Self::FrameRendered
+    FramePresented {
+        /// Window that presented the frame.
+        window: WindowId,
+        /// Presented frame, is the requested frame, even if the frame was presented after a newer frame.
+        frame: FrameId,
+    },
+
     /// Window moved, resized, or minimized/maximized etc.
     ///
     /// This event aggregates events moves, resizes and other state changes into a
@@ -347,6 +361,8 @@ pub enum Event {
         /// Axis.
         axis: AxisId,
         /// Motion value.
+        ///
+        /// Coalesced events sum the values of the same axis.
         value: f64,
     },
     /// Touch event has been received.
@@ -375,6 +391,18 @@ pub enum Event {
     /// The available monitors have changed.
     MonitorsChanged(Vec<(MonitorId, MonitorInfo)>),
 
+    /// The actual exclusive fullscreen video mode of the window changed.
+    ///
+    /// This is `Some(mode)` when the window enters exclusive fullscreen and `None` when it exits, including
+    /// when the operating system drops exclusive mode, for example on *alt-tab*. Note that the window state
+    /// can still be fullscreen (borderless) when the exclusive mode is lost.
+    WindowVideoModeChanged {
+        /// Window that changed video mode.
+        window: WindowId,
+        /// Actual video mode, or `None` if the window is not in exclusive fullscreen.
+        mode: Option<VideoMode>,
+    },
+
     /// The window has been requested to close.
     WindowCloseRequested(WindowId),
     /// The window has closed.
@@ -421,6 +449,11 @@ pub enum Event {
     ImageEncoded {
         /// The image that finished encoding.
         image: ImageId,
+        /// The [`Api::encode_image_opts`] request, or [`ImageEncodeId::INVALID`] for [`Api::encode_image`].
+        ///
+        /// [`Api::encode_image_opts`]: crate::Api::encode_image_opts
+        /// [`Api::encode_image`]: crate::Api::encode_image
+        request: ImageEncodeId,
         /// The format of the encoded data.
         format: Txt,
         /// The encoded image data.
@@ -430,6 +463,11 @@ pub enum Event {
     ImageEncodeError {
         /// The image that failed to encode.
         image: ImageId,
+        /// The [`Api::encode_image_opts`] request, or [`ImageEncodeId::INVALID`] for [`Api::encode_image`].
+        ///
+        /// [`Api::encode_image_opts`]: crate::Api::encode_image_opts
+        /// [`Api::encode_image`]: crate::Api::encode_image
+        request: ImageEncodeId,
         /// The encoded format that was requested.
         format: Txt,
         /// The error message.
@@ -547,6 +585,25 @@ pub enum Event {
     /// System low memory warning, some platforms may kill the app if it does not release memory.
     LowMemory,
 
+    /// Renderers GPU memory usage is high, the app should release resources like cached images.
+    ///
+    /// The usage is the sum of the GPU memory reported by all window and headless surface renderers, it is
+    /// checked at most once a second after a frame is rendered. The level is [`Low`] when the usage reaches
+    /// [`MemoryPressureThresholds::low`] and [`Critical`] when it reaches [`MemoryPressureThresholds::critical`],
+    /// the thresholds can be set using [`Api::set_memory_pressure_thresholds`], the default is 1 GiB and 2 GiB.
+    ///
+    /// The event is send when the pressure level changes to a higher level. A level only drops after the usage is
+    /// below 80% of its threshold, so that usage oscillating around a threshold does not flood the app with events,
+    /// after the level drops the event is send again on the next high usage.
+    ///
+    /// [`Low`]: MemoryPressureLevel::Low
+    /// [`Critical`]: MemoryPressureLevel::Critical
+    /// [`Api::set_memory_pressure_thresholds`]: crate::Api::set_memory_pressure_thresholds
+    MemoryPressure {
+        /// Pressure level.
+        level: MemoryPressureLevel,
+    },
+
     /// An internal component panicked, but the view-process managed to recover from it without
     /// needing to respawn.
     RecoveredFromComponentPanic {
@@ -597,6 +654,24 @@ impl Event {
                 *delta += n_delta;
             }
 
+            // analog axis motion.
+            (
+                AxisMotion {
+                    window,
+                    device,
+                    axis,
+                    value,
+                },
+                AxisMotion {
+                    window: n_window,
+                    device: n_device,
+                    axis: n_axis,
+                    value: n_value,
+                },
+            ) if *window == n_window && *device == n_device && *axis == n_axis => {
+                *value += n_value;
+            }
+
             // wheel scroll.
             (
                 MouseWheel {
@@ -770,6 +845,51 @@ impl Event {
     }
 }
 
+/// Memory pressure level of [`Event::MemoryPressure`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum MemoryPressureLevel {
+    /// Memory usage is high, cold resources should be released.
+    Low,
+    /// Memory usage is critical, allocations may fail, all resources not in use should be released.
+    Critical,
+}
+
+/// GPU memory usage thresholds of [`Event::MemoryPressure`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MemoryPressureThresholds {
+    /// Bytes used by all renderers for the [`MemoryPressureLevel::Low`] level.
+    ///
+    /// Is 1 GiB by default.
+    pub low: usize,
+    /// Bytes used by all renderers for the [`MemoryPressureLevel::Critical`] level.
+    ///
+    /// Is 2 GiB by default.
+    pub critical: usize,
+}
+impl Default for MemoryPressureThresholds {
+    fn default() -> Self {
+        Self {
+            low: 1024 * 1024 * 1024,
+            critical: 2 * 1024 * 1024 * 1024,
+        }
+    }
+}
+impl MemoryPressureThresholds {
+    /// Gets the pressure level for the `used` bytes.
+    ///
+    /// The `current` level only drops after `used` is below 80% of its threshold.
+    pub fn level(&self, used: usize, current: Option<MemoryPressureLevel>) -> Option<MemoryPressureLevel> {
+        let release = |threshold: usize| threshold / 5 * 4;
+        if used >= self.critical || (current == Some(MemoryPressureLevel::Critical) && used >= release(self.critical)) {
+            Some(MemoryPressureLevel::Critical)
+        } else if used >= self.low || (current.is_some() && used >= release(self.low)) {
+            Some(MemoryPressureLevel::Low)
+        } else {
+            None
+        }
+    }
+}
+
 /// The View-Process disconnected or has not finished initializing, try again after the *inited* event.
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub struct ViewProcessOffline;
@@ -1016,4 +1136,67 @@ mod tests {
             assert_eq!(k.name(), &format!("{:?}", k));
         }
     }
+
+    #[test]
+    fn memory_pressure_level_hysteresis() {
+        let t = MemoryPressureThresholds { low: 100, critical: 200 };
+
+        assert_eq!(t.level(50, None), None);
+        assert_eq!(t.level(100, None), Some(MemoryPressureLevel::Low));
+        assert_eq!(t.level(200, None), Some(MemoryPressureLevel::Critical));
+
+        // only drops below 80% of the threshold
+        assert_eq!(t.level(90, Some(MemoryPressureLevel::Low)), Some(MemoryPressureLevel::Low));
+        assert_eq!(t.level(79, Some(MemoryPressureLevel::Low)), None);
+        assert_eq!(
+            t.level(170, Some(MemoryPressureLevel::Critical)),
+            Some(MemoryPressureLevel::Critical)
+        );
+        assert_eq!(t.level(150, Some(MemoryPressureLevel::Critical)), Some(MemoryPressureLevel::Low));
+        assert_eq!(t.level(85, Some(MemoryPressureLevel::Critical)), Some(MemoryPressureLevel::Low));
+        assert_eq!(t.level(10, Some(MemoryPressureLevel::Critical)), None);
+    }
+
+    fn axis_motion(axis: u32, value: f64) -> Event {
+        Event::AxisMotion {
+            window: WindowId::first(),
+            device: DeviceId::first(),
+            axis: AxisId(axis),
+            value,
+        }
+    }
+
+    #[test]
+    fn coalesce_axis_motion_same_axis() {
+        let mut ev = axis_motion(0, 1.5);
+        assert!(ev.coalesce(axis_motion(0, 2.0)).is_ok());
+
+        match ev {
+            Event::AxisMotion { axis, value, .. } => {
+                assert_eq!(axis, AxisId(0));
+                assert_eq!(value, 3.5);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn coalesce_axis_motion_other_axis() {
+        let mut ev = axis_motion(0, 1.5);
+        match ev.coalesce(axis_motion(1, 2.0)) {
+            Err(Event::AxisMotion { axis, value, .. }) => {
+                assert_eq!(axis, AxisId(1));
+                assert_eq!(value, 2.0);
+            }
+            r => panic!("expected `Err(AxisMotion)`, was {r:?}"),
+        }
+
+        match ev {
+            Event::AxisMotion { axis, value, .. } => {
+                assert_eq!(axis, AxisId(0));
+                assert_eq!(value, 1.5);
+            }
+            _ => unreachable!(),
+        }
+    }
 }
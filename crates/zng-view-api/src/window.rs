@@ -92,6 +92,25 @@ zng_var::impl_from_and_into_var! {
     fn from(some: RenderMode) -> Option<RenderMode>;
 }
 
+/// Information about the graphics adapter and driver used by a window renderer.
+///
+/// This is useful for diagnostics, the strings are reported by the driver and have no standard format.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RendererInfo {
+    /// Actual render mode used by the renderer.
+    pub render_mode: RenderMode,
+    /// Graphics API vendor name.
+    pub vendor: Txt,
+    /// Graphics adapter name.
+    pub renderer: Txt,
+    /// Graphics API version, including driver version on most platforms.
+    pub version: Txt,
+    /// If the renderer does not use a hardware accelerated context.
+    ///
+    /// This is `true` for the software render mode and for drivers that are known software rasterizers.
+    pub is_software: bool,
+}
+
 /// Configuration of a new headless surface.
 ///
 /// Headless surfaces are always [`capture_mode`] enabled.
@@ -370,12 +389,24 @@ pub struct WindowRequest {
     pub visible: bool,
     /// Window taskbar icon visibility.
     pub taskbar_visible: bool,
-    /// If the window is "top-most".
-    pub always_on_top: bool,
+    /// Window z-order level.
+    pub level: WindowLevel,
     /// If the user can move the window.
     pub movable: bool,
     /// If the user can resize the window.
     pub resizable: bool,
+    /// If the restore rectangle is clamped to the current monitor.
+    ///
+    /// See [`Api::set_clamp_to_monitor`] for details.
+    ///
+    /// [`Api::set_clamp_to_monitor`]: crate::Api::set_clamp_to_monitor
+    pub clamp_to_monitor: bool,
+    /// Width/height ratio the window maintains when it is resized by the user.
+    ///
+    /// See [`Api::set_aspect_ratio`] for details.
+    ///
+    /// [`Api::set_aspect_ratio`]: crate::Api::set_aspect_ratio
+    pub aspect_ratio: Option<f32>,
     /// Window icon.
     pub icon: Option<ImageId>,
     /// Window cursor icon and visibility.
@@ -835,6 +866,22 @@ impl WindowState {
     }
 }
 
+/// Window z-order level.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, Default)]
+pub enum WindowLevel {
+    /// Window is ordered normally with other windows.
+    #[default]
+    Normal,
+    /// Window stays below other windows, like a desktop widget.
+    ///
+    /// Note this only applies to other windows that are not also "always-on-bottom".
+    AlwaysOnBottom,
+    /// Window stays on top of other windows, also called "top-most".
+    ///
+    /// Note this only applies to other windows that are not also "always-on-top".
+    AlwaysOnTop,
+}
+
 /// [`Event::FrameRendered`] payload.
 ///
 /// [`Event::FrameRendered`]: crate::Event::FrameRendered
@@ -8,7 +8,7 @@ fn tp_licenses() {
     #[cfg(feature = "bundle_licenses")]
     {
         #[allow(unused_mut)]
-        let mut licenses = zng_tp_licenses::collect_cargo_about("../../.cargo/about.toml");
+        let mut licenses = zng_tp_licenses::collect_cargo_about_cached("../../.cargo/about.toml", "");
 
         avif_licenses(&mut licenses);
 
@@ -59,6 +59,14 @@ pub trait DisplayListExtension {
     fn update(&mut self, args: &mut DisplayExtensionUpdateArgs) {
         let _ = args;
     }
+
+    /// Get the Webrender image key for an external image registered by an extension.
+    ///
+    /// Returns `None` if the image is not registered, the display item is skipped in this case.
+    fn external_image_key(&mut self, id: wr::ExternalImageId) -> Option<wr::ImageKey> {
+        let _ = id;
+        None
+    }
 }
 impl DisplayListExtension for () {
     fn push_display_item(&mut self, args: &mut DisplayExtensionItemArgs) {
@@ -469,6 +477,28 @@ fn display_item_to_webrender(
             sc.pop_spatial();
         }
 
+        DisplayItem::PushStickyFrame {
+            id,
+            frame_rect,
+            margins,
+            vertical_offset_bounds: (v_min, v_max),
+            horizontal_offset_bounds: (h_min, h_max),
+        } => {
+            let m = |m: Option<zng_unit::Px>| m.map(|m| m.0 as f32);
+            let spatial_id = wr_list.define_sticky_frame(
+                sc.spatial_id(),
+                frame_rect.to_wr(),
+                wr::euclid::SideOffsets2D::new(m(margins.top), m(margins.right), m(margins.bottom), m(margins.left)),
+                wr::StickyOffsetBounds::new(v_min.0 as f32, v_max.0 as f32),
+                wr::StickyOffsetBounds::new(h_min.0 as f32, h_max.0 as f32),
+                wr::units::LayoutVector2D::zero(),
+                id.to_wr(),
+                None,
+            );
+            sc.push_spatial(spatial_id);
+        }
+        DisplayItem::PopStickyFrame => sc.pop_spatial(),
+
         DisplayItem::PushStackingContext {
             blend_mode,
             transform_style,
@@ -551,6 +581,27 @@ fn display_item_to_webrender(
             sc.pop_clip();
         }
 
+        DisplayItem::PushShadow {
+            offset,
+            color,
+            blur_radius,
+        } => {
+            let clip = sc.clip_chain_id(wr_list);
+            wr_list.push_shadow(
+                &wr::SpaceAndClipInfo {
+                    spatial_id: sc.spatial_id(),
+                    clip_chain_id: clip,
+                },
+                wr::Shadow {
+                    offset: offset.cast_unit(),
+                    color: color.to_wr(),
+                    blur_radius: *blur_radius,
+                },
+                true,
+            );
+        }
+        DisplayItem::PopAllShadows => wr_list.pop_all_shadows(),
+
         DisplayItem::SetBackfaceVisibility { visible } => {
             sc.set_backface_visibility(*visible);
         }
@@ -762,6 +813,29 @@ fn display_item_to_webrender(
             }
         }
 
+        DisplayItem::ExternalImage { clip_rect, image_id } => {
+            if let Some(key) = ext.external_image_key(wr::ExternalImageId(image_id.0)) {
+                let bounds = clip_rect.to_wr();
+                let clip = sc.clip_chain_id(wr_list);
+                let props = wr::CommonItemProperties {
+                    clip_rect: bounds,
+                    clip_chain_id: clip,
+                    spatial_id: sc.spatial_id(),
+                    flags: sc.primitive_flags(),
+                };
+                wr_list.push_image(
+                    &props,
+                    bounds,
+                    wr::ImageRendering::Auto,
+                    wr::AlphaType::Alpha,
+                    key,
+                    wr::ColorF::WHITE,
+                );
+            } else {
+                tracing::error!("external image {image_id:?} not registered");
+            }
+        }
+
         DisplayItem::LinearGradient {
             clip_rect,
             start_point,
@@ -10,7 +10,8 @@ use std::{any::Any, sync::Arc};
 
 use webrender::api::{
     units::TexelRect, AsyncBlobImageRasterizer, BlobImageHandler, BlobImageParams, BlobImageRequest, BlobImageResult, DocumentId,
-    ExternalImageId, PipelineId,
+    ExternalImageData, ExternalImageId, ExternalImageType, ImageBufferKind, ImageDescriptor, ImageDescriptorFlags, ImageFormat, ImageKey,
+    PipelineId,
 };
 use webrender::{DebugFlags, RenderApi};
 use zng_unit::{Factor, PxSize};
@@ -53,6 +54,16 @@ pub trait ViewExtension: Send + Any {
 
     /// System warning low memory, release unused memory, caches.
     fn low_memory(&mut self) {}
+
+    /// Change the pending extension `event` to incorporate the `new` event or returns `new` if both cannot be coalesced.
+    ///
+    /// High-frequency events send by the extension are buffered like the built-in input events, this method is called
+    /// when two events of this extension are notified in sequence within the coalescing interval. By default does
+    /// not coalesce.
+    fn coalesce_event(&mut self, event: &mut ApiExtensionPayload, new: ApiExtensionPayload) -> Result<(), ApiExtensionPayload> {
+        let _ = event;
+        Err(new)
+    }
 }
 
 /// Represents a view extension associated with a headed window instance.
@@ -474,11 +485,14 @@ pub struct RendererInitedArgs<'a> {
 #[derive(Default)]
 pub struct ExternalImages {
     images: Vec<Arc<crate::image_cache::ImageData>>,
+    keys: Vec<(ExternalImageId, ImageKey)>,
+    removed_keys: Vec<ImageKey>,
 }
 impl ExternalImages {
     /// Register an OpenGL texture.
     ///
-    /// Returns an `ExternalImageId` that can be used in display lists.
+    /// Returns an `ExternalImageId` that can be used in display lists, the app-process can render
+    /// the texture using `FrameBuilder::push_external_image`.
     ///
     /// The id is only valid in the same renderer, and the `texture` must be generated by the same
     /// GL API used by the renderer. Note that you must manage the `texture` lifetime, [`unregister`]
@@ -513,6 +527,52 @@ impl ExternalImages {
         if let Some(i) = self.images.iter().position(|img| ExternalImageId(Arc::as_ptr(img) as u64) == id) {
             self.images.swap_remove(i);
         }
+        if let Some(i) = self.keys.iter().position(|(k, _)| *k == id) {
+            let (_, key) = self.keys.swap_remove(i);
+            self.removed_keys.push(key);
+        }
+    }
+
+    /// Gets or generates the image key used to render the `id` in `push_external_image` display items.
+    pub(crate) fn image_key(&mut self, id: ExternalImageId, api: &RenderApi, txn: &mut webrender::Transaction) -> Option<ImageKey> {
+        if let Some((_, key)) = self.keys.iter().find(|(k, _)| *k == id) {
+            return Some(*key);
+        }
+
+        let img = self.images.iter().find(|img| ExternalImageId(Arc::as_ptr(img) as u64) == id)?;
+        let (descriptor, image_type) = match &**img {
+            crate::image_cache::ImageData::RawData { descriptor, .. } => (*descriptor, ExternalImageType::Buffer),
+            crate::image_cache::ImageData::NativeTexture { uv, .. } => (
+                ImageDescriptor::new(
+                    (uv.uv1.x - uv.uv0.x).abs() as i32,
+                    (uv.uv1.y - uv.uv0.y).abs() as i32,
+                    ImageFormat::RGBA8,
+                    ImageDescriptorFlags::empty(),
+                ),
+                ExternalImageType::TextureHandle(ImageBufferKind::Texture2D),
+            ),
+        };
+
+        let key = api.generate_image_key();
+        txn.add_image(
+            key,
+            descriptor,
+            webrender::api::ImageData::External(ExternalImageData {
+                id,
+                channel_index: 0,
+                image_type,
+            }),
+            None,
+        );
+        self.keys.push((id, key));
+        Some(key)
+    }
+
+    /// Deletes the image keys of unregistered images.
+    pub(crate) fn cleanup_keys(&mut self, txn: &mut webrender::Transaction) {
+        for key in self.removed_keys.drain(..) {
+            txn.delete_image(key);
+        }
     }
 
     fn register(&mut self, img: crate::image_cache::ImageData) -> ExternalImageId {
@@ -822,6 +882,18 @@ impl ViewExtensions {
         }
     }
 
+    pub(crate) fn coalesce_event(
+        &mut self,
+        id: ApiExtensionId,
+        event: &mut ApiExtensionPayload,
+        new: ApiExtensionPayload,
+    ) -> Result<(), ApiExtensionPayload> {
+        match self.exts.get_mut(id.index()) {
+            Some(ext) => ext.coalesce_event(event, new),
+            None => Err(new),
+        }
+    }
+
     pub(crate) fn on_low_memory(&mut self) {
         for ext in self.exts.iter_mut() {
             ext.low_memory();
@@ -895,6 +967,8 @@ pub(crate) struct DisplayListExtAdapter<'a> {
 
 impl<'a> DisplayListExtension for DisplayListExtAdapter<'a> {
     fn display_list_start(&mut self, args: &mut DisplayExtensionArgs) {
+        self.external_images.cleanup_keys(self.transaction);
+
         for (_, ext) in self.extensions.iter_mut() {
             ext.render_start(&mut RenderArgs {
                 frame_id: self.frame_id,
@@ -979,6 +1053,10 @@ impl<'a> DisplayListExtension for DisplayListExtAdapter<'a> {
             }
         }
     }
+
+    fn external_image_key(&mut self, id: ExternalImageId) -> Option<ImageKey> {
+        self.external_images.image_key(id, self.api, self.transaction)
+    }
 }
 
 pub(crate) struct BlobExtensionsImgHandler(pub Vec<Box<dyn BlobExtension>>);
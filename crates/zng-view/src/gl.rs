@@ -11,7 +11,7 @@ use glutin::{
 use rustc_hash::FxHashSet;
 use winit::{dpi::PhysicalSize, event_loop::ActiveEventLoop};
 use zng_txt::ToTxt as _;
-use zng_view_api::window::{RenderMode, WindowId};
+use zng_view_api::window::{RenderMode, RendererInfo, WindowId};
 
 use raw_window_handle::*;
 
@@ -91,7 +91,10 @@ impl GlContextManager {
             }));
 
             let error = match r {
-                Ok(Ok(r)) => return r,
+                Ok(Ok(mut r)) => {
+                    tracing::info!("[{}] headed renderer {:?}", config.name(), r.1.renderer_info());
+                    return r;
+                }
                 Ok(Err(e)) => e,
                 Err(panic) => {
                     let component = match config.mode {
@@ -146,7 +149,10 @@ impl GlContextManager {
             }));
 
             let error = match r {
-                Ok(Ok(ctx)) => return ctx,
+                Ok(Ok(mut ctx)) => {
+                    tracing::info!("[{}] headless renderer {:?}", config.name(), ctx.renderer_info());
+                    return ctx;
+                }
                 Ok(Err(e)) => e,
                 Err(panic) => {
                     let component = match config.mode {
@@ -543,6 +549,35 @@ impl GlContext {
         false
     }
 
+    /// Query the graphics adapter and driver info.
+    pub(crate) fn renderer_info(&mut self) -> RendererInfo {
+        self.make_current();
+
+        let vendor = self.gl.get_string(gl::VENDOR);
+        let renderer = self.gl.get_string(gl::RENDERER);
+        let version = self.gl.get_string(gl::VERSION);
+
+        // known software rasterizers that can be selected by the driver even when requesting hardware.
+        let is_software = self.is_software()
+            || [
+                "llvmpipe",
+                "softpipe",
+                "swiftshader",
+                "software rasterizer",
+                "microsoft basic render",
+            ]
+            .iter()
+            .any(|s| renderer.to_lowercase().contains(s));
+
+        RendererInfo {
+            render_mode: self.render_mode,
+            vendor: vendor.into(),
+            renderer: renderer.into(),
+            version: version.into(),
+            is_software,
+        }
+    }
+
     pub fn is_current(&self) -> bool {
         Some(self.id) == self.current.get()
     }
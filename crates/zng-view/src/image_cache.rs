@@ -1,4 +1,10 @@
-use std::{fmt, sync::Arc};
+use std::{
+    collections::hash_map::Entry,
+    fmt,
+    io::{self, Write},
+    sync::Arc,
+    time::Duration,
+};
 
 use webrender::api::{ImageDescriptor, ImageDescriptorFlags, ImageFormat};
 use winit::{
@@ -8,8 +14,11 @@ use winit::{
 use zng_txt::{formatx, ToTxt, Txt};
 use zng_unit::{Px, PxPoint, PxSize};
 use zng_view_api::{
-    image::{ImageDataFormat, ImageDownscale, ImageId, ImageLoadedData, ImageMaskMode, ImagePpi, ImageRequest},
-    ipc::{IpcBytes, IpcBytesReceiver},
+    image::{
+        EncodeOptions, ImageDataFormat, ImageDownscale, ImageEncodeId, ImageFrameData, ImageId, ImageLoadedData, ImageMaskMode, ImagePpi,
+        ImageRequest,
+    },
+    ipc::{self, IpcBytes, IpcBytesReceiver, IpcBytesSender},
     Event,
 };
 
@@ -59,17 +68,34 @@ pub(crate) const DECODERS: &[&str] = &[
 pub(crate) struct ImageCache {
     app_sender: AppEventSender,
     images: FxHashMap<ImageId, Image>,
+    // animated images, all frames and delays, including the first frame.
+    animations: FxHashMap<ImageId, Vec<(ImageId, Duration)>>,
+    // extra references to images, the first reference is implicit.
+    retains: FxHashMap<ImageId, usize>,
     image_id_gen: ImageId,
+    // file extensions of decoders that can be used.
+    enabled_decoders: Arc<Vec<Txt>>,
 }
 impl ImageCache {
     pub fn new(app_sender: AppEventSender) -> Self {
         Self {
             app_sender,
             images: FxHashMap::default(),
+            animations: FxHashMap::default(),
+            retains: FxHashMap::default(),
             image_id_gen: ImageId::first(),
+            enabled_decoders: Arc::new(DECODERS.iter().map(|&s| Txt::from_static(s)).collect()),
         }
     }
 
+    /// Restrict the decoders used by [`add`] and [`add_pro`] to the `formats`.
+    ///
+    /// [`add`]: Self::add
+    /// [`add_pro`]: Self::add_pro
+    pub fn set_enabled_decoders(&mut self, formats: Vec<Txt>) {
+        self.enabled_decoders = Arc::new(formats.into_iter().map(|f| f.to_lowercase().into()).collect());
+    }
+
     pub fn add(
         &mut self,
         ImageRequest {
@@ -83,7 +109,9 @@ impl ImageCache {
         let id = self.image_id_gen.incr();
 
         let app_sender = self.app_sender.clone();
+        let enabled_decoders = self.enabled_decoders.clone();
         rayon::spawn(move || {
+            let mut animation = (Duration::ZERO, vec![]);
             let r = match format {
                 ImageDataFormat::Bgra8 { size, ppi } => {
                     let expected_len = size.width.0 as usize * size.height.0 as usize * 4;
@@ -125,7 +153,7 @@ impl ImageCache {
                         Ok((data, size, None, is_opaque, true))
                     }
                 }
-                fmt => match Self::get_format_and_size(&fmt, &data[..]) {
+                fmt => match Self::get_format_and_size(&fmt, &data[..], &enabled_decoders) {
                     Ok((fmt, mut size)) => {
                         let decoded_len = size.width.0 as u64 * size.height.0 as u64 * 4;
                         if decoded_len > max_decoded_len {
@@ -142,8 +170,11 @@ impl ImageCache {
                                 ppi: None,
                                 is_mask: false,
                             }));
-                            match Self::image_decode(&data[..], fmt, downscale) {
-                                Ok(img) => Ok(Self::convert_decoded(img, mask)),
+                            match Self::image_decode_all(&data[..], fmt, downscale, mask, max_decoded_len) {
+                                Ok((img, frame_delay, frames)) => {
+                                    animation = (frame_delay, frames);
+                                    Ok(img)
+                                }
                                 Err(e) => Err(e.to_txt()),
                             }
                         }
@@ -154,6 +185,7 @@ impl ImageCache {
 
             match r {
                 Ok((pixels, size, ppi, is_opaque, is_mask)) => {
+                    let (frame_delay, frames) = animation;
                     let _ = app_sender.send(AppEvent::ImageLoaded(ImageLoadedData {
                         id,
                         pixels,
@@ -161,6 +193,8 @@ impl ImageCache {
                         ppi,
                         is_opaque,
                         is_mask,
+                        frame_delay,
+                        frames,
                     }));
                 }
                 Err(e) => {
@@ -184,6 +218,7 @@ impl ImageCache {
     ) -> ImageId {
         let id = self.image_id_gen.incr();
         let app_sender = self.app_sender.clone();
+        let enabled_decoders = self.enabled_decoders.clone();
         rayon::spawn(move || {
             // crate `images` does not do progressive decode.
             let mut full = vec![];
@@ -207,6 +242,10 @@ impl ImageCache {
                 ImageDataFormat::MimeType(t) => t.strip_prefix("image/").and_then(image::ImageFormat::from_extension),
                 ImageDataFormat::Unknown => None,
             };
+            if let Some(Err(error)) = format.map(|f| Self::check_decoder(&enabled_decoders, f)) {
+                let _ = app_sender.send(AppEvent::Notify(Event::ImageLoadError { image: id, error }));
+                return;
+            }
 
             let mut pending = true;
             while pending {
@@ -235,6 +274,10 @@ impl ImageCache {
                             }
                         } else if is_encoded {
                             format = image::guess_format(&full).ok();
+                            if let Some(Err(error)) = format.map(|f| Self::check_decoder(&enabled_decoders, f)) {
+                                let _ = app_sender.send(AppEvent::Notify(Event::ImageLoadError { image: id, error }));
+                                return;
+                            }
                         }
                     }
                     Err(_) => {
@@ -245,9 +288,8 @@ impl ImageCache {
             }
 
             if let Some(fmt) = format {
-                match Self::image_decode(&full[..], fmt, downscale) {
-                    Ok(img) => {
-                        let (pixels, size, ppi, is_opaque, is_mask) = Self::convert_decoded(img, mask);
+                match Self::image_decode_all(&full[..], fmt, downscale, mask, max_decoded_len) {
+                    Ok(((pixels, size, ppi, is_opaque, is_mask), frame_delay, frames)) => {
                         let _ = app_sender.send(AppEvent::ImageLoaded(ImageLoadedData {
                             id,
                             pixels,
@@ -255,6 +297,8 @@ impl ImageCache {
                             ppi,
                             is_opaque,
                             is_mask,
+                            frame_delay,
+                            frames,
                         }));
                     }
                     Err(e) => {
@@ -274,6 +318,8 @@ impl ImageCache {
                     ppi,
                     is_opaque,
                     is_mask: false,
+                    frame_delay: Duration::ZERO,
+                    frames: vec![],
                 }));
             } else {
                 let _ = app_sender.send(AppEvent::Notify(Event::ImageLoadError {
@@ -285,8 +331,37 @@ impl ImageCache {
         id
     }
 
+    /// Decrement the image reference count, the image is removed when it reaches zero.
     pub fn forget(&mut self, id: ImageId) {
+        if let Entry::Occupied(mut e) = self.retains.entry(id) {
+            *e.get_mut() -= 1;
+            if *e.get() == 0 {
+                e.remove();
+            }
+            return;
+        }
         self.images.remove(&id);
+        self.animations.remove(&id);
+    }
+
+    /// Increment the image reference count, each call must be paired with a [`forget`] call.
+    ///
+    /// Does nothing if the image is not loaded.
+    ///
+    /// [`forget`]: Self::forget
+    pub fn retain(&mut self, id: ImageId) {
+        if self.images.contains_key(&id) {
+            *self.retains.entry(id).or_insert(0) += 1;
+        }
+    }
+
+    /// Number of references to the image, zero if the image is not loaded.
+    pub fn ref_count(&self, id: ImageId) -> usize {
+        if self.images.contains_key(&id) {
+            1 + self.retains.get(&id).copied().unwrap_or(0)
+        } else {
+            0
+        }
     }
 
     pub fn get(&self, id: ImageId) -> Option<&Image> {
@@ -294,7 +369,33 @@ impl ImageCache {
     }
 
     /// Called after receive and decode completes correctly.
-    pub(crate) fn loaded(&mut self, data: ImageLoadedData) {
+    pub(crate) fn loaded(&mut self, mut data: ImageLoadedData) {
+        // animation frames are decoded in the worker thread without ID.
+        for frame in &mut data.frames {
+            frame.id = self.image_id_gen.incr();
+
+            let mut flags = ImageDescriptorFlags::empty();
+            if frame.is_opaque {
+                flags |= ImageDescriptorFlags::IS_OPAQUE
+            }
+            self.images.insert(
+                frame.id,
+                Image(Arc::new(ImageData::RawData {
+                    size: data.size,
+                    pixels: frame.pixels.clone(),
+                    descriptor: ImageDescriptor::new(data.size.width.0, data.size.height.0, ImageFormat::BGRA8, flags),
+                    ppi: data.ppi,
+                })),
+            );
+        }
+        if !data.frames.is_empty() {
+            let frames = Some((data.id, data.frame_delay))
+                .into_iter()
+                .chain(data.frames.iter().map(|f| (f.id, f.delay)))
+                .collect();
+            self.animations.insert(data.id, frames);
+        }
+
         let mut flags = ImageDescriptorFlags::empty(); //ImageDescriptorFlags::ALLOW_MIPMAPS;
         if data.is_opaque {
             flags |= ImageDescriptorFlags::IS_OPAQUE
@@ -318,7 +419,16 @@ impl ImageCache {
         let _ = self.app_sender.send(AppEvent::Notify(Event::ImageLoaded(data)));
     }
 
-    fn get_format_and_size(fmt: &ImageDataFormat, data: &[u8]) -> Result<(image::ImageFormat, PxSize), Txt> {
+    /// Returns an error if the decoder for the `format` is not enabled.
+    fn check_decoder(enabled_decoders: &[Txt], format: image::ImageFormat) -> Result<(), Txt> {
+        if format.extensions_str().iter().any(|e| enabled_decoders.iter().any(|d| d == e)) {
+            Ok(())
+        } else {
+            Err(formatx!("image format {format:?} decoder is not enabled"))
+        }
+    }
+
+    fn get_format_and_size(fmt: &ImageDataFormat, data: &[u8], enabled_decoders: &[Txt]) -> Result<(image::ImageFormat, PxSize), Txt> {
         let fmt = match fmt {
             ImageDataFormat::FileExtension(ext) => image::ImageFormat::from_extension(ext.as_str()),
             ImageDataFormat::MimeType(t) => t.strip_prefix("image/").and_then(image::ImageFormat::from_extension),
@@ -336,6 +446,7 @@ impl ImageCache {
 
         match reader.format() {
             Some(fmt) => {
+                Self::check_decoder(enabled_decoders, fmt)?;
                 let (w, h) = reader.into_dimensions().map_err(|e| e.to_string())?;
                 Ok((fmt, PxSize::new(Px(w as i32), Px(h as i32))))
             }
@@ -343,14 +454,107 @@ impl ImageCache {
         }
     }
 
+    /// Decode the image, if the image is animated and is not decoding to a mask also decodes the other frames.
+    ///
+    /// Returns the first frame, the first frame delay and the other frames without ID.
+    fn image_decode_all(
+        buf: &[u8],
+        format: image::ImageFormat,
+        downscale: Option<ImageDownscale>,
+        mask: Option<ImageMaskMode>,
+        max_decoded_len: u64,
+    ) -> image::ImageResult<(RawLoadedImg, Duration, Vec<ImageFrameData>)> {
+        if mask.is_none() {
+            if let Some(frames) = Self::image_decode_frames(buf, format, max_decoded_len)? {
+                let mut frames = frames
+                    .into_iter()
+                    .map(|(img, delay)| (Self::convert_decoded(Self::image_downscale(img, downscale), None), delay));
+                let (first, frame_delay) = frames.next().unwrap();
+                let frames: Vec<_> = frames
+                    .map(|((pixels, _, _, is_opaque, _), delay)| ImageFrameData {
+                        id: ImageId::INVALID, // set by `loaded`
+                        delay,
+                        is_opaque,
+                        pixels,
+                    })
+                    .collect();
+                // single frame is a still image
+                let frame_delay = if frames.is_empty() { Duration::ZERO } else { frame_delay };
+                return Ok((first, frame_delay, frames));
+            }
+        }
+
+        let img = Self::image_decode(buf, format, downscale)?;
+        Ok((Self::convert_decoded(img, mask), Duration::ZERO, vec![]))
+    }
+
+    /// Decode all frames if the image format supports animation.
+    ///
+    /// Returns `None` if the format or image does not support animation, returns the single frame of still images
+    /// of formats that support animation, so that they are not decoded again.
+    fn image_decode_frames(
+        buf: &[u8],
+        format: image::ImageFormat,
+        max_decoded_len: u64,
+    ) -> image::ImageResult<Option<Vec<(image::DynamicImage, Duration)>>> {
+        use image::AnimationDecoder as _;
+
+        let buf = std::io::Cursor::new(buf);
+        let frames = match format {
+            image::ImageFormat::Gif => image::codecs::gif::GifDecoder::new(buf)?.into_frames(),
+            image::ImageFormat::Png => {
+                let decoder = image::codecs::png::PngDecoder::new(buf)?;
+                if !decoder.is_apng()? {
+                    return Ok(None);
+                }
+                decoder.apng()?.into_frames()
+            }
+            image::ImageFormat::WebP => {
+                let decoder = image::codecs::webp::WebPDecoder::new(buf)?;
+                if !decoder.has_animation() {
+                    return Ok(None);
+                }
+                decoder.into_frames()
+            }
+            _ => return Ok(None),
+        };
+
+        let mut r = vec![];
+        let mut decoded_len = 0u64;
+        for frame in frames {
+            let frame = frame?;
+            let delay = Duration::from(frame.delay());
+            let img = frame.into_buffer();
+
+            decoded_len += img.width() as u64 * img.height() as u64 * 4;
+            if decoded_len > max_decoded_len {
+                return Err(image::ImageError::Limits(image::error::LimitError::from_kind(
+                    image::error::LimitErrorKind::InsufficientMemory,
+                )));
+            }
+
+            r.push((image::DynamicImage::ImageRgba8(img), delay));
+        }
+
+        if r.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(r))
+        }
+    }
+
     fn image_decode(buf: &[u8], format: image::ImageFormat, downscale: Option<ImageDownscale>) -> image::ImageResult<image::DynamicImage> {
         let buf = std::io::Cursor::new(buf);
 
         let mut reader = image::io::Reader::new(buf);
         reader.set_format(format);
         reader.no_limits();
-        let mut image = reader.decode()?;
+        let image = reader.decode()?;
 
+        Ok(Self::image_downscale(image, downscale))
+    }
+
+    fn image_downscale(mut image: image::DynamicImage, downscale: Option<ImageDownscale>) -> image::DynamicImage {
         if let Some(s) = downscale {
             let (img_w, img_h) = (image.width(), image.height());
             match s {
@@ -371,7 +575,7 @@ impl ImageCache {
             }
         }
 
-        Ok(image)
+        image
     }
 
     fn convert_decoded(image: image::DynamicImage, mask: Option<ImageMaskMode>) -> RawLoadedImg {
@@ -805,12 +1009,52 @@ impl ImageCache {
         )
     }
 
-    pub fn encode(&self, id: ImageId, format: Txt) {
+    pub fn encode(&self, id: ImageId, request: ImageEncodeId, format: Txt, opts: EncodeOptions) {
+        let sender = self.app_sender.clone();
+        self.encode_impl(id, format, opts, vec![], move |id, format, r| {
+            let ev = match r {
+                Ok(data) => Event::ImageEncoded {
+                    image: id,
+                    request,
+                    format,
+                    data: IpcBytes::from_vec(data),
+                },
+                Err(error) => Event::ImageEncodeError {
+                    image: id,
+                    request,
+                    format,
+                    error,
+                },
+            };
+            let _ = sender.send(AppEvent::Notify(ev));
+        })
+    }
+
+    pub fn encode_streaming(&self, id: ImageId, format: Txt, opts: EncodeOptions) -> IpcBytesReceiver {
+        let (sender, receiver) = ipc::bytes_channel();
+        self.encode_impl(id, format, opts, IpcChunkWriter::new(sender), |_, _, r| match r {
+            Ok(output) => {
+                // app-process may have dropped the receiver.
+                let _ = output.finish();
+            }
+            // output is dropped, the receiver disconnects without the finish package.
+            Err(e) => tracing::error!("{e}"),
+        });
+        receiver
+    }
+
+    /// Encode the image to `output` in a worker thread, `on_encoded` is called with the `output` or error.
+    fn encode_impl<W: EncodeOutput + Send + 'static>(
+        &self,
+        id: ImageId,
+        format: Txt,
+        opts: EncodeOptions,
+        mut output: W,
+        on_encoded: impl FnOnce(ImageId, Txt, Result<W, Txt>) + Send + 'static,
+    ) {
         if !ENCODERS.contains(&format.as_str()) {
             let error = formatx!("cannot encode `{id:?}` to `{format}`, unknown format");
-            let _ = self
-                .app_sender
-                .send(AppEvent::Notify(Event::ImageEncodeError { image: id, format, error }));
+            on_encoded(id, format, Err(error));
             return;
         }
 
@@ -819,28 +1063,31 @@ impl ImageCache {
             debug_assert!(fmt.can_write());
 
             let img = img.clone();
-            let sender = self.app_sender.clone();
+            // animated GIF encodes all frames, frames can be forgotten by the app-process, these are skipped.
+            let frames: Vec<_> = match (fmt, self.animations.get(&id)) {
+                (image::ImageFormat::Gif, Some(frames)) => frames
+                    .iter()
+                    .filter_map(|(id, delay)| self.get(*id).map(|img| (img.clone(), *delay)))
+                    .collect(),
+                _ => vec![],
+            };
             rayon::spawn(move || {
-                let mut data = vec![];
-                match img.encode(fmt, &mut data) {
-                    Ok(_) => {
-                        let _ = sender.send(AppEvent::Notify(Event::ImageEncoded {
-                            image: id,
-                            format,
-                            data: IpcBytes::from_vec(data),
-                        }));
-                    }
+                let r = if frames.len() > 1 {
+                    Image::encode_gif_animation(&frames, opts, &mut output)
+                } else {
+                    img.encode(fmt, opts, &mut output)
+                };
+                match r {
+                    Ok(_) => on_encoded(id, format, Ok(output)),
                     Err(e) => {
                         let error = formatx!("failed to encode `{id:?}` to `{format}`, {e}");
-                        let _ = sender.send(AppEvent::Notify(Event::ImageEncodeError { image: id, format, error }));
+                        on_encoded(id, format, Err(error));
                     }
                 }
             })
         } else {
             let error = formatx!("cannot encode `{id:?}` to `{format}`, image not found");
-            let _ = self
-                .app_sender
-                .send(AppEvent::Notify(Event::ImageEncodeError { image: id, format, error }));
+            on_encoded(id, format, Err(error));
         }
     }
 
@@ -849,6 +1096,65 @@ impl ImageCache {
     }
 }
 
+/// Image encoder output.
+pub(crate) trait EncodeOutput: Write {
+    /// Call `encode` with a writer that can seek, for encoders that require it.
+    fn write_seekable(&mut self, encode: impl FnOnce(&mut dyn WriteSeek) -> image::ImageResult<()>) -> image::ImageResult<()>;
+}
+pub(crate) trait WriteSeek: Write + io::Seek {}
+impl<W: Write + io::Seek> WriteSeek for W {}
+impl EncodeOutput for Vec<u8> {
+    fn write_seekable(&mut self, encode: impl FnOnce(&mut dyn WriteSeek) -> image::ImageResult<()>) -> image::ImageResult<()> {
+        encode(&mut io::Cursor::new(self))
+    }
+}
+
+/// Encoder output that sends the data in chunks.
+struct IpcChunkWriter {
+    sender: IpcBytesSender,
+    chunk: Vec<u8>,
+}
+impl IpcChunkWriter {
+    const CHUNK_LEN: usize = 1024 * 1024;
+
+    fn new(sender: IpcBytesSender) -> Self {
+        Self { sender, chunk: vec![] }
+    }
+
+    /// Send the pending data and the empty finish package.
+    fn finish(mut self) -> io::Result<()> {
+        self.flush()?;
+        self.sender.send(vec![]).map_err(|e| io::Error::new(io::ErrorKind::BrokenPipe, e))
+    }
+}
+impl Write for IpcChunkWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.chunk.extend_from_slice(buf);
+        if self.chunk.len() >= Self::CHUNK_LEN {
+            self.flush()?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.chunk.is_empty() {
+            // empty package signals finish, only send in `finish`.
+            let chunk = std::mem::take(&mut self.chunk);
+            self.sender.send(chunk).map_err(|e| io::Error::new(io::ErrorKind::BrokenPipe, e))?;
+        }
+        Ok(())
+    }
+}
+impl EncodeOutput for IpcChunkWriter {
+    fn write_seekable(&mut self, encode: impl FnOnce(&mut dyn WriteSeek) -> image::ImageResult<()>) -> image::ImageResult<()> {
+        // buffer the full encoded data, the chunks already sent can't be changed.
+        let mut data = io::Cursor::new(vec![]);
+        encode(&mut data)?;
+        self.write_all(&data.into_inner())?;
+        Ok(())
+    }
+}
+
 /// (pixels, size, ppi, is_opaque, is_mask)
 type RawLoadedImg = (IpcBytes, PxSize, Option<ImagePpi>, bool, bool);
 pub(crate) enum ImageData {
@@ -915,37 +1221,10 @@ impl Image {
             ImageData::NativeTexture { .. } => unreachable!(),
         };
 
-        let width = size.width.0 as u32;
-        let height = size.height.0 as u32;
-        if width == 0 || height == 0 || self.0.is_mask() {
+        if self.0.is_mask() {
             None
         } else {
-            let r = if width > 255 || height > 255 {
-                // resize to max 255
-                let mut buf = pixels[..].to_vec();
-                // BGRA to RGBA
-                buf.chunks_exact_mut(4).for_each(|c| c.swap(0, 2));
-                let img = image::ImageBuffer::from_raw(width, height, buf).unwrap();
-                let img = image::DynamicImage::ImageRgba8(img);
-                img.resize(255, 255, image::imageops::FilterType::Lanczos3);
-
-                use image::GenericImageView;
-                let (width, height) = img.dimensions();
-                let buf = img.into_rgba8().into_raw();
-                winit::window::Icon::from_rgba(buf, width, height)
-            } else {
-                let mut buf = pixels[..].to_vec();
-                // BGRA to RGBA
-                buf.chunks_exact_mut(4).for_each(|c| c.swap(0, 2));
-                winit::window::Icon::from_rgba(buf, width, height)
-            };
-            match r {
-                Ok(i) => Some(i),
-                Err(e) => {
-                    tracing::error!("failed to convert image to custom icon, {e}");
-                    None
-                }
-            }
+            icon_from_bgra(*size, &pixels[..])
         }
     }
 
@@ -977,7 +1256,7 @@ impl Image {
         }
     }
 
-    pub fn encode(&self, format: image::ImageFormat, buffer: &mut Vec<u8>) -> image::ImageResult<()> {
+    pub fn encode<W: EncodeOutput>(&self, format: image::ImageFormat, opts: EncodeOptions, buffer: &mut W) -> image::ImageResult<()> {
         let (size, pixels, ppi) = match &*self.0 {
             ImageData::RawData { size, pixels, ppi, .. } => (size, pixels, ppi),
             ImageData::NativeTexture { .. } => unreachable!(),
@@ -1002,7 +1281,7 @@ impl Image {
             if is_opaque {
                 img = image::DynamicImage::ImageRgb8(img.to_rgb8());
             }
-            img.write_to(&mut std::io::Cursor::new(buffer), format)?;
+            Self::write_with_opts(&img, format, opts, buffer)?;
 
             return Ok(());
         }
@@ -1019,7 +1298,10 @@ impl Image {
 
         match format {
             ImageFormat::Jpeg => {
-                let mut jpg = codecs::jpeg::JpegEncoder::new(buffer);
+                let mut jpg = match opts.quality {
+                    Some(q) => codecs::jpeg::JpegEncoder::new_with_quality(buffer, q.clamp(1, 100)),
+                    None => codecs::jpeg::JpegEncoder::new(buffer),
+                };
                 if let Some(ppi) = ppi {
                     jpg.set_pixel_density(codecs::jpeg::PixelDensity {
                         density: (ppi.x as u16, ppi.y as u16),
@@ -1036,7 +1318,7 @@ impl Image {
                 if let Some(ppi) = ppi {
                     let mut png_bytes = vec![];
 
-                    img.write_to(&mut std::io::Cursor::new(&mut png_bytes), ImageFormat::Png)?;
+                    Self::write_with_opts(&img, ImageFormat::Png, opts, &mut png_bytes)?;
 
                     let mut png = img_parts::png::Png::from_bytes(png_bytes.into()).unwrap();
 
@@ -1059,7 +1341,7 @@ impl Image {
 
                     png.encoder().write_to(buffer)?;
                 } else {
-                    img.write_to(&mut std::io::Cursor::new(buffer), ImageFormat::Png)?;
+                    Self::write_with_opts(&img, ImageFormat::Png, opts, buffer)?;
                 }
             }
             _ => {
@@ -1069,13 +1351,68 @@ impl Image {
                 if is_opaque {
                     img = image::DynamicImage::ImageRgb8(img.to_rgb8());
                 }
-                img.write_to(&mut std::io::Cursor::new(buffer), format)?;
+                Self::write_with_opts(&img, format, opts, buffer)?;
             }
         }
 
         Ok(())
     }
 
+    fn write_with_opts<W: EncodeOutput>(
+        img: &image::DynamicImage,
+        format: image::ImageFormat,
+        opts: EncodeOptions,
+        buffer: &mut W,
+    ) -> image::ImageResult<()> {
+        use image::codecs::*;
+
+        match (format, opts) {
+            (image::ImageFormat::Jpeg, EncodeOptions { quality: Some(q), .. }) => {
+                img.write_with_encoder(jpeg::JpegEncoder::new_with_quality(buffer, q.clamp(1, 100)))
+            }
+            (image::ImageFormat::Jpeg, _) => img.write_with_encoder(jpeg::JpegEncoder::new(buffer)),
+            (image::ImageFormat::Png, EncodeOptions { compression: Some(c), .. }) => img.write_with_encoder(
+                png::PngEncoder::new_with_quality(buffer, png_compression(c), png::FilterType::Adaptive),
+            ),
+            (image::ImageFormat::Png, _) => img.write_with_encoder(png::PngEncoder::new(buffer)),
+            (image::ImageFormat::Gif, EncodeOptions { loop_count: Some(_), .. }) => {
+                let mut encoder = gif::GifEncoder::new(buffer);
+                encoder.set_repeat(gif_repeat(opts.loop_count))?;
+                encoder.encode_frame(image::Frame::new(img.to_rgba8()))
+            }
+            (image::ImageFormat::Gif, _) => img.write_with_encoder(gif::GifEncoder::new(buffer)),
+            (image::ImageFormat::WebP, _) => img.write_with_encoder(webp::WebPEncoder::new_lossless(buffer)),
+            (image::ImageFormat::Ico, _) => img.write_with_encoder(ico::IcoEncoder::new(buffer)),
+            (image::ImageFormat::Bmp, _) => img.write_with_encoder(bmp::BmpEncoder::new(buffer)),
+            (image::ImageFormat::Hdr, _) => img.write_with_encoder(hdr::HdrEncoder::new(buffer)),
+            (image::ImageFormat::Pnm, _) => img.write_with_encoder(pnm::PnmEncoder::new(buffer)),
+            (image::ImageFormat::Qoi, _) => img.write_with_encoder(qoi::QoiEncoder::new(buffer)),
+            (image::ImageFormat::Farbfeld, _) => img.write_with_encoder(farbfeld::FarbfeldEncoder::new(buffer)),
+            // encoders that require seeking, like EXR
+            _ => buffer.write_seekable(|w| img.write_to(w, format)),
+        }
+    }
+
+    /// Encode all `frames` to an animated GIF.
+    pub fn encode_gif_animation<W: Write>(frames: &[(Image, Duration)], opts: EncodeOptions, buffer: &mut W) -> image::ImageResult<()> {
+        use image::codecs::gif::GifEncoder;
+
+        let mut gif = GifEncoder::new(buffer);
+        gif.set_repeat(gif_repeat(opts.loop_count))?;
+        for (img, delay) in frames {
+            let (size, pixels) = match &*img.0 {
+                ImageData::RawData { size, pixels, .. } => (size, pixels),
+                ImageData::NativeTexture { .. } => unreachable!(),
+            };
+            let mut buf = pixels[..].to_vec();
+            // BGRA to RGBA
+            buf.chunks_exact_mut(4).for_each(|c| c.swap(0, 2));
+            let rgba = image::RgbaImage::from_raw(size.width.0 as u32, size.height.0 as u32, buf).unwrap();
+            gif.encode_frame(image::Frame::from_parts(rgba, 0, 0, image::Delay::from_saturating_duration(*delay)))?;
+        }
+        Ok(())
+    }
+
     #[allow(unused)]
     pub fn size(&self) -> PxSize {
         match &*self.0 {
@@ -1107,7 +1444,7 @@ mod external {
         },
         RenderApi,
     };
-    use zng_view_api::image::ImageTextureId;
+    use zng_view_api::image::{ImageId, ImageTextureId};
 
     use super::{Image, ImageData};
 
@@ -1178,34 +1515,48 @@ mod external {
     #[derive(Default)]
     pub(crate) struct ImageUseMap {
         id_tex: FxHashMap<ExternalImageId, (ImageTextureId, Image)>,
-        tex_id: FxHashMap<ImageTextureId, ExternalImageId>,
+        tex_id: FxHashMap<ImageTextureId, (ExternalImageId, ImageId)>,
     }
     impl ImageUseMap {
-        pub fn new_use(&mut self, image: &Image, document_id: DocumentId, api: &mut RenderApi) -> ImageTextureId {
+        /// Returns the texture and if it is new, each new texture holds a reference to the `image_id`.
+        pub fn new_use(
+            &mut self,
+            image_id: ImageId,
+            image: &Image,
+            document_id: DocumentId,
+            api: &mut RenderApi,
+        ) -> (ImageTextureId, bool) {
             let id = image.external_id();
             match self.id_tex.entry(id) {
-                Entry::Occupied(e) => e.get().0,
+                Entry::Occupied(e) => (e.get().0, false),
                 Entry::Vacant(e) => {
                     let key = api.generate_image_key();
                     let tex_id = ImageTextureId::from_raw(key.1);
                     e.insert((tex_id, image.clone())); // keep the image Arc alive, we expect this in `WrImageCache`.
-                    self.tex_id.insert(tex_id, id);
+                    self.tex_id.insert(tex_id, (id, image_id));
 
                     let mut txn = webrender::Transaction::new();
                     txn.add_image(key, image.descriptor(), image.data(), None);
                     api.send_transaction(document_id, txn);
 
-                    tex_id
+                    (tex_id, true)
                 }
             }
         }
 
-        /// Returns if needs to update.
-        pub fn update_use(&mut self, texture_id: ImageTextureId, image: &Image, document_id: DocumentId, api: &mut RenderApi) {
+        /// Returns the previous image if it was replaced by `image_id`.
+        pub fn update_use(
+            &mut self,
+            texture_id: ImageTextureId,
+            image_id: ImageId,
+            image: &Image,
+            document_id: DocumentId,
+            api: &mut RenderApi,
+        ) -> Option<ImageId> {
             if let Entry::Occupied(mut e) = self.tex_id.entry(texture_id) {
                 let id = image.external_id();
-                if *e.get() != id {
-                    let prev_id = e.insert(id);
+                if e.get().0 != id {
+                    let (prev_id, prev_image_id) = e.insert((id, image_id));
                     self.id_tex.remove(&prev_id).unwrap();
                     self.id_tex.insert(id, (texture_id, image.clone()));
 
@@ -1217,26 +1568,91 @@ mod external {
                         &ImageDirtyRect::All,
                     );
                     api.send_transaction(document_id, txn);
+
+                    return Some(prev_image_id);
                 }
             }
+            None
         }
 
-        pub fn delete(&mut self, texture_id: ImageTextureId, document_id: DocumentId, api: &mut RenderApi) {
-            if let Some(id) = self.tex_id.remove(&texture_id) {
+        /// Returns the image that was used by the texture.
+        pub fn delete(&mut self, texture_id: ImageTextureId, document_id: DocumentId, api: &mut RenderApi) -> Option<ImageId> {
+            if let Some((id, image_id)) = self.tex_id.remove(&texture_id) {
                 let _img = self.id_tex.remove(&id); // remove but keep alive until the transaction is done.
                 let mut txn = webrender::Transaction::new();
                 txn.delete_image(ImageKey(api.get_namespace_id(), texture_id.get()));
                 api.send_transaction(document_id, txn);
+                Some(image_id)
+            } else {
+                None
             }
         }
+
+        /// Images referenced by textures, one entry per texture.
+        pub fn used_images(&self) -> impl Iterator<Item = ImageId> + '_ {
+            self.tex_id.values().map(|(_, id)| *id)
+        }
     }
 }
 pub(crate) use external::{ImageUseMap, WrImageCache};
 
+fn png_compression(level: u8) -> image::codecs::png::CompressionType {
+    use image::codecs::png::CompressionType;
+    match level {
+        0..=3 => CompressionType::Fast,
+        4..=6 => CompressionType::Default,
+        _ => CompressionType::Best,
+    }
+}
+
+fn gif_repeat(loop_count: Option<u16>) -> image::codecs::gif::Repeat {
+    match loop_count {
+        Some(n) => image::codecs::gif::Repeat::Finite(n),
+        None => image::codecs::gif::Repeat::Infinite,
+    }
+}
+
+/// Generate a window icon from BGRA8 pixels.
+pub(crate) fn icon_from_bgra(size: PxSize, pixels: &[u8]) -> Option<Icon> {
+    let width = size.width.0 as u32;
+    let height = size.height.0 as u32;
+    if width == 0 || height == 0 {
+        None
+    } else {
+        let r = if width > 255 || height > 255 {
+            // resize to max 255
+            let mut buf = pixels.to_vec();
+            // BGRA to RGBA
+            buf.chunks_exact_mut(4).for_each(|c| c.swap(0, 2));
+            let img = image::ImageBuffer::from_raw(width, height, buf).unwrap();
+            let img = image::DynamicImage::ImageRgba8(img);
+            let img = img.resize(255, 255, image::imageops::FilterType::Lanczos3);
+
+            use image::GenericImageView;
+            let (width, height) = img.dimensions();
+            let buf = img.into_rgba8().into_raw();
+            winit::window::Icon::from_rgba(buf, width, height)
+        } else {
+            let mut buf = pixels.to_vec();
+            // BGRA to RGBA
+            buf.chunks_exact_mut(4).for_each(|c| c.swap(0, 2));
+            winit::window::Icon::from_rgba(buf, width, height)
+        };
+        match r {
+            Ok(i) => Some(i),
+            Err(e) => {
+                tracing::error!("failed to convert image to custom icon, {e}");
+                None
+            }
+        }
+    }
+}
+
 mod capture {
-    use std::sync::Arc;
+    use std::{sync::Arc, time::Duration};
 
     use webrender::api::{ImageDescriptor, ImageDescriptorFlags, ImageFormat};
+    use winit::window::Icon;
     use zng_txt::formatx;
     use zng_unit::{Factor, PxRect};
     use zng_view_api::{
@@ -1329,13 +1745,34 @@ mod capture {
             data
         }
 
-        fn frame_image_data_impl(
-            &mut self,
-            gl: &dyn gleam::gl::Gl,
-            rect: PxRect,
-            scale_factor: Factor,
-            mask: Option<ImageMaskMode>,
-        ) -> ImageLoadedData {
+        /// Create a window icon from the current rendered frame.
+        pub fn frame_icon(gl: &dyn gleam::gl::Gl, rect: PxRect) -> Option<Icon> {
+            let buf = Self::read_frame_pixels(gl, rect);
+            super::icon_from_bgra(rect.size, &buf)
+        }
+
+        /// Compute a deterministic hash of the current rendered frame pixels.
+        pub fn frame_pixels_hash(gl: &dyn gleam::gl::Gl, rect: PxRect) -> u64 {
+            let buf = Self::read_frame_pixels(gl, rect);
+
+            // explicit 64-bit FxHash, the result must not depend on the platform or process.
+            const K: u64 = 0x517c_c1b7_2722_0a95;
+            let mut h = 0u64;
+            let mut add = |v: u64| h = (h.rotate_left(5) ^ v).wrapping_mul(K);
+            add(rect.size.width.0 as u64);
+            add(rect.size.height.0 as u64);
+            let mut chunks = buf.chunks_exact(8);
+            for c in &mut chunks {
+                add(u64::from_le_bytes(c.try_into().unwrap()));
+            }
+            for &b in chunks.remainder() {
+                add(b as u64);
+            }
+            h
+        }
+
+        /// Read BGRA8 pixels from the current rendered frame, top-to-bottom.
+        fn read_frame_pixels(gl: &dyn gleam::gl::Gl, rect: PxRect) -> Vec<u8> {
             let pixels_flipped = gl.read_pixels(
                 rect.origin.x.0,
                 rect.origin.y.0,
@@ -1350,6 +1787,17 @@ mod capture {
             for (px, buf) in pixels_flipped.chunks_exact(stride).rev().zip(buf.chunks_exact_mut(stride)) {
                 buf.copy_from_slice(px);
             }
+            buf
+        }
+
+        fn frame_image_data_impl(
+            &mut self,
+            gl: &dyn gleam::gl::Gl,
+            rect: PxRect,
+            scale_factor: Factor,
+            mask: Option<ImageMaskMode>,
+        ) -> ImageLoadedData {
+            let mut buf = Self::read_frame_pixels(gl, rect);
 
             if let Some(mask) = mask {
                 for bgra in buf.chunks_exact_mut(4) {
@@ -1377,6 +1825,8 @@ mod capture {
                     is_opaque,
                     is_mask,
                     pixels,
+                    frame_delay: Duration::ZERO,
+                    frames: vec![],
                 }
             } else {
                 let is_opaque = buf.chunks_exact(4).all(|bgra| bgra[3] == 255);
@@ -1401,6 +1851,8 @@ mod capture {
                     is_opaque,
                     pixels: data,
                     is_mask: false,
+                    frame_delay: Duration::ZERO,
+                    frames: vec![],
                 }
             }
         }
@@ -134,17 +134,18 @@ use zng_txt::Txt;
 use zng_unit::{Dip, DipPoint, DipRect, DipSize, Factor, Px, PxPoint, PxRect, PxToDip};
 use zng_view_api::{
     api_extension::{ApiExtensionId, ApiExtensionPayload},
+    config::ColorScheme,
     dialog::{DialogId, FileDialog, MsgDialog, MsgDialogResponse},
     font::{FontFaceId, FontId, FontOptions, FontVariationName},
-    image::{ImageId, ImageLoadedData, ImageMaskMode, ImageRequest, ImageTextureId},
+    image::{EncodeOptions, ImageEncodeId, ImageId, ImageLoadedData, ImageMaskMode, ImageRequest, ImageTextureId},
     ipc::{IpcBytes, IpcBytesReceiver},
     keyboard::{Key, KeyCode, KeyState},
     mouse::ButtonId,
     touch::{TouchId, TouchUpdate},
     window::{
-        CursorIcon, CursorImage, EventCause, EventFrameRendered, FocusIndicator, FrameRequest, FrameUpdateRequest, FrameWaitId,
-        HeadlessOpenData, HeadlessRequest, MonitorId, MonitorInfo, VideoMode, WindowChanged, WindowId, WindowOpenData, WindowRequest,
-        WindowState, WindowStateAll,
+        CursorIcon, CursorImage, EventCause, EventFrameRendered, FocusIndicator, FrameId, FrameRequest, FrameUpdateRequest, FrameWaitId,
+        HeadlessOpenData, HeadlessRequest, MonitorId, MonitorInfo, RendererInfo, VideoMode, WindowChanged, WindowId, WindowLevel,
+        WindowOpenData, WindowRequest, WindowState, WindowStateAll,
     },
     Inited, *,
 };
@@ -182,9 +183,9 @@ pub fn view_process_main() {
     }
 
     if config.headless {
-        App::run_headless(c, ext);
+        App::run_headless(c, ext, None);
     } else {
-        App::run_headed(c, ext);
+        App::run_headed(c, ext, None);
     }
 
     zng_env::exit(0)
@@ -204,6 +205,9 @@ pub extern "C" fn extern_view_process_main() {
 /// In this mode the app only uses a single process, reducing the memory footprint, but it is also not
 /// resilient to video driver crashes, the view server **does not** respawn in this mode.
 ///
+/// This function returns after the app exits, the view event loop shuts down when the app requests exit or
+/// when `run_app` returns or panics, then the app thread is joined. Panics in the app thread are propagated.
+///
 /// # Panics
 ///
 /// Panics if not called in the main thread, this is a requirement of some operating systems.
@@ -220,23 +224,14 @@ pub fn run_same_process(run_app: impl FnOnce() + Send + 'static) {
 
 /// Like [`run_same_process`] but with custom API extensions.
 pub fn run_same_process_extended(run_app: impl FnOnce() + Send + 'static, ext: fn() -> ViewExtensions) {
+    let (shutdown_sender, shutdown_receiver) = flume::bounded::<()>(1);
     let app_thread = thread::Builder::new()
         .name("app".to_owned())
         .spawn(move || {
-            // SAFETY: we exit the process in case of panic.
-            if let Err(e) = std::panic::catch_unwind(std::panic::AssertUnwindSafe(run_app)) {
-                thread::spawn(|| {
-                    // Sometimes the channel does not disconnect on panic,
-                    // observed this issue on a panic in `AppExtension::init`.
-                    //
-                    // This workaround ensures that don't become a zombie process.
-                    thread::sleep(std::time::Duration::from_secs(5));
-                    eprintln!("run_same_process did not exit after 5s of a fatal panic, exiting now");
-                    zng_env::exit(101);
-                });
-                // Propagate panic in case the normal disconnect/shutdown handler works.
-                std::panic::resume_unwind(e);
-            }
+            // the view event loop exits when this is dropped, even if the channel
+            // with the app did not disconnect, this ensures we don't become a zombie process.
+            let _shutdown = shutdown_sender;
+            run_app()
         })
         .unwrap();
 
@@ -246,9 +241,9 @@ pub fn run_same_process_extended(run_app: impl FnOnce() + Send + 'static, ext: f
     let c = ipc::connect_view_process(config.server_name).expect("failed to connect to app in same process");
 
     if config.headless {
-        App::run_headless(c, ext());
+        App::run_headless(c, ext(), Some(shutdown_receiver));
     } else {
-        App::run_headed(c, ext());
+        App::run_headed(c, ext(), Some(shutdown_receiver));
     }
 
     if let Err(p) = app_thread.join() {
@@ -325,6 +320,8 @@ pub(crate) struct App {
 
     dialog_id_gen: DialogId,
 
+    image_encode_id_gen: ImageEncodeId,
+
     resize_frame_wait_id_gen: FrameWaitId,
 
     coalescing_event: Option<(Event, Instant)>,
@@ -347,6 +344,10 @@ pub(crate) struct App {
 
     config_listener_exit: Option<Box<dyn FnOnce()>>,
 
+    memory_pressure: Option<MemoryPressureLevel>,
+    memory_pressure_thresholds: MemoryPressureThresholds,
+    memory_pressure_checked: Instant,
+
     exited: bool,
 }
 impl fmt::Debug for App {
@@ -416,7 +417,10 @@ impl winit::application::ApplicationHandler<AppEvent> for App {
         }
 
         match event {
-            WindowEvent::RedrawRequested => self.windows[i].redraw(),
+            WindowEvent::RedrawRequested => {
+                self.windows[i].redraw();
+                self.notify_frames_presented(id);
+            }
             WindowEvent::Resized(_) => {
                 let size = if let Some(size) = self.windows[i].resized() {
                     size
@@ -457,6 +461,10 @@ impl winit::application::ApplicationHandler<AppEvent> for App {
                     self.notify(Event::WindowChanged(WindowChanged::state_changed(id, state, EventCause::System)));
                 }
 
+                if let Some(mode) = self.windows[i].video_mode_change() {
+                    self.notify(Event::WindowVideoModeChanged { window: id, mode });
+                }
+
                 if let Some(handle) = self.windows[i].monitor_change() {
                     let m_id = self.monitor_handle_to_id(&handle);
 
@@ -541,6 +549,10 @@ impl winit::application::ApplicationHandler<AppEvent> for App {
                     self.notify(Event::WindowChanged(WindowChanged::state_changed(id, state, EventCause::System)));
                 }
 
+                if let Some(mode) = self.windows[i].video_mode_change() {
+                    self.notify(Event::WindowVideoModeChanged { window: id, mode });
+                }
+
                 self.notify(Event::WindowChanged(WindowChanged::moved(
                     id,
                     global_position,
@@ -559,7 +571,10 @@ impl winit::application::ApplicationHandler<AppEvent> for App {
                 self.notify(Event::WindowCloseRequested(id))
             }
             WindowEvent::Destroyed => {
-                self.windows.remove(i);
+                let w = self.windows.remove(i);
+                for image_id in w.used_images() {
+                    self.image_cache.forget(image_id);
+                }
                 self.notify(Event::WindowClosed(id));
             }
             WindowEvent::DroppedFile(file) => {
@@ -575,6 +590,11 @@ impl winit::application::ApplicationHandler<AppEvent> for App {
                 self.notify(Event::HoveredFileCancelled(id))
             }
             WindowEvent::Focused(mut focused) => {
+                // exclusive fullscreen can be dropped by the system on focus loss (alt-tab).
+                if let Some(mode) = self.windows[i].video_mode_change() {
+                    self.notify(Event::WindowVideoModeChanged { window: id, mode });
+                }
+
                 if self.windows[i].focused_changed(&mut focused) {
                     if focused {
                         self.notify(Event::FocusChanged { prev: None, new: Some(id) });
@@ -864,6 +884,10 @@ impl winit::application::ApplicationHandler<AppEvent> for App {
                 for w in &mut self.windows {
                     w.redraw();
                 }
+                let ids: Vec<_> = self.windows.iter().map(|w| w.id()).collect();
+                for id in ids {
+                    self.notify_frames_presented(id);
+                }
             }
             AppEvent::InitDeviceEvents(enabled) => {
                 self.init_device_events(enabled, Some(winit_loop));
@@ -977,7 +1001,7 @@ impl App {
         }
     }
 
-    pub fn run_headless(ipc: ipc::ViewChannels, ext: ViewExtensions) {
+    pub fn run_headless(ipc: ipc::ViewChannels, ext: ViewExtensions, shutdown: Option<flume::Receiver<()>>) {
         tracing::info!("running headless view-process");
 
         gl::warmup();
@@ -992,6 +1016,9 @@ impl App {
             ext,
         );
         app.headless = true;
+        if let Some(s) = shutdown {
+            app.start_shutdown_listener(s);
+        }
 
         let winit_span = tracing::trace_span!("winit::EventLoop::new").entered();
         let event_loop = EventLoop::new().unwrap();
@@ -1096,7 +1123,7 @@ impl App {
         }
     }
 
-    pub fn run_headed(ipc: ipc::ViewChannels, ext: ViewExtensions) {
+    pub fn run_headed(ipc: ipc::ViewChannels, ext: ViewExtensions, shutdown: Option<flume::Receiver<()>>) {
         tracing::info!("running headed view-process");
 
         gl::warmup();
@@ -1115,6 +1142,9 @@ impl App {
             ext,
         );
         app.start_receiving(ipc.request_receiver);
+        if let Some(s) = shutdown {
+            app.start_shutdown_listener(s);
+        }
 
         app.config_listener_exit = config::spawn_listener(app.app_sender.clone());
 
@@ -1159,6 +1189,7 @@ impl App {
             devices: vec![],
             device_id_gen: DeviceId::INVALID,
             dialog_id_gen: DialogId::INVALID,
+            image_encode_id_gen: ImageEncodeId::INVALID,
             resize_frame_wait_id_gen: FrameWaitId::INVALID,
             coalescing_event: None,
             cursor_entered_expect_move: Vec::with_capacity(1),
@@ -1169,6 +1200,9 @@ impl App {
             pending_modifiers_update: None,
             pending_modifiers_focus_clear: false,
             config_listener_exit: None,
+            memory_pressure: None,
+            memory_pressure_thresholds: MemoryPressureThresholds::default(),
+            memory_pressure_checked: Instant::now(),
 
             #[cfg(not(windows))]
             arboard: None,
@@ -1187,6 +1221,15 @@ impl App {
         });
     }
 
+    /// Exits the event loop when the `shutdown` sender disconnects, used by [`run_same_process`].
+    fn start_shutdown_listener(&self, shutdown: flume::Receiver<()>) {
+        let app_sender = self.app_sender.clone();
+        thread::spawn(move || {
+            let _ = shutdown.recv();
+            let _ = app_sender.send(AppEvent::ParentProcessExited);
+        });
+    }
+
     fn monitor_handle_to_id(&mut self, handle: &MonitorHandle) -> MonitorId {
         if let Some((id, _)) = self.monitors.iter().find(|(_, h)| h == handle) {
             *id
@@ -1317,6 +1360,7 @@ impl App {
                 let size = w.size();
                 self.notify(Event::WindowChanged(WindowChanged::resized(window_id, size, EventCause::App, None)));
             }
+            self.notify_frames_presented(window_id);
         } else if let Some(s) = self.surfaces.iter_mut().find(|w| w.id() == window_id) {
             let (frame_id, image) = s.on_frame_ready(msg, &mut self.image_cache);
 
@@ -1324,7 +1368,50 @@ impl App {
                 window: window_id,
                 frame: frame_id,
                 frame_image: image,
-            }))
+            }));
+            self.notify_frames_presented(window_id);
+        }
+
+        self.check_memory_pressure();
+    }
+
+    /// Notify `MemoryPressure` if the renderers GPU memory usage changed to a higher pressure level.
+    ///
+    /// Collecting the renderers memory report is not cheap, so this checks at most once a second.
+    fn check_memory_pressure(&mut self) {
+        let now = Instant::now();
+        if now.duration_since(self.memory_pressure_checked) < Duration::from_secs(1) {
+            return;
+        }
+        self.memory_pressure_checked = now;
+
+        let used: usize =
+            self.windows.iter().map(|w| w.gpu_memory()).sum::<usize>() + self.surfaces.iter().map(|s| s.gpu_memory()).sum::<usize>();
+        let level = self.memory_pressure_thresholds.level(used, self.memory_pressure);
+
+        let prev = std::mem::replace(&mut self.memory_pressure, level);
+        if let Some(level) = level {
+            if prev.map(|p| p < level).unwrap_or(true) {
+                tracing::warn!(
+                    "renderers GPU memory usage is {} MiB, notifying {level:?} memory pressure",
+                    used / 1024 / 1024
+                );
+                self.notify(Event::MemoryPressure { level });
+            }
+        }
+    }
+
+    /// Notify `FramePresented` for requested frames that are now presented.
+    fn notify_frames_presented(&mut self, id: WindowId) {
+        let frames = if let Some(w) = self.windows.iter_mut().find(|w| w.id() == id) {
+            w.take_frames_presented()
+        } else if let Some(s) = self.surfaces.iter_mut().find(|s| s.id() == id) {
+            s.take_frames_presented()
+        } else {
+            return;
+        };
+        for frame in frames {
+            self.notify(Event::FramePresented { window: id, frame });
         }
     }
 
@@ -1334,7 +1421,17 @@ impl App {
             let r = if now.saturating_duration_since(timestamp) >= Duration::from_millis(16) {
                 Err(event)
             } else {
-                coal.coalesce(event)
+                match coal.coalesce(event) {
+                    // custom events, extensions may coalesce.
+                    Err(Event::ExtensionEvent(n_id, n_payload)) => match &mut coal {
+                        Event::ExtensionEvent(id, payload) if *id == n_id => self
+                            .exts
+                            .coalesce_event(n_id, payload, n_payload)
+                            .map_err(|p| Event::ExtensionEvent(n_id, p)),
+                        _ => Err(Event::ExtensionEvent(n_id, n_payload)),
+                    },
+                    r => r,
+                }
             };
             match r {
                 Ok(()) => self.coalescing_event = Some((coal, timestamp)),
@@ -1631,10 +1728,16 @@ impl Api for App {
 
         self.assert_started();
         if let Some(i) = self.windows.iter().position(|w| w.id() == id) {
-            let _ = self.windows.swap_remove(i);
+            let w = self.windows.swap_remove(i);
+            for image_id in w.used_images() {
+                self.image_cache.forget(image_id);
+            }
         }
         if let Some(i) = self.surfaces.iter().position(|w| w.id() == id) {
-            let _ = self.surfaces.swap_remove(i);
+            let s = self.surfaces.swap_remove(i);
+            for image_id in s.used_images() {
+                self.image_cache.forget(image_id);
+            }
         }
     }
 
@@ -1650,6 +1753,14 @@ impl Api for App {
         self.with_window(id, |w| w.set_always_on_top(always_on_top), || ())
     }
 
+    fn set_window_level(&mut self, id: WindowId, level: WindowLevel) {
+        self.with_window(id, |w| w.set_window_level(level), || ())
+    }
+
+    fn set_preferred_theme(&mut self, id: WindowId, theme: Option<ColorScheme>) {
+        self.with_window(id, |w| w.set_preferred_theme(theme), || ())
+    }
+
     fn set_movable(&mut self, id: WindowId, movable: bool) {
         self.with_window(id, |w| w.set_movable(movable), || ())
     }
@@ -1658,6 +1769,14 @@ impl Api for App {
         self.with_window(id, |w| w.set_resizable(resizable), || ())
     }
 
+    fn set_clamp_to_monitor(&mut self, id: WindowId, clamp: bool) {
+        self.with_window(id, |w| w.set_clamp_to_monitor(clamp), || ())
+    }
+
+    fn set_aspect_ratio(&mut self, id: WindowId, ratio: Option<f32>) {
+        self.with_window(id, |w| w.set_aspect_ratio(ratio), || ())
+    }
+
     fn set_taskbar_visible(&mut self, id: WindowId, visible: bool) {
         self.with_window(id, |w| w.set_taskbar_visible(visible), || ())
     }
@@ -1690,6 +1809,10 @@ impl Api for App {
         }
     }
 
+    fn renderer_info(&mut self, id: WindowId) -> RendererInfo {
+        with_window_or_surface!(self, id, |w| w.renderer_info(), || RendererInfo::default())
+    }
+
     fn set_video_mode(&mut self, id: WindowId, mode: VideoMode) {
         self.with_window(id, |w| w.set_video_mode(mode), || ())
     }
@@ -1699,6 +1822,11 @@ impl Api for App {
         self.with_window(id, |w| w.set_icon(icon), || ())
     }
 
+    fn set_window_icon_from_surface(&mut self, id: WindowId, surface: WindowId) {
+        let icon = self.surfaces.iter_mut().find(|s| s.id() == surface).and_then(|s| s.frame_icon());
+        self.with_window(id, |w| w.set_icon(icon), || ())
+    }
+
     fn set_focus_indicator(&mut self, id: WindowId, request: Option<FocusIndicator>) {
         self.with_window(id, |w| w.set_focus_request(request), || ())
     }
@@ -1752,6 +1880,14 @@ impl Api for App {
         image_cache::ENCODERS.iter().map(|&s| Txt::from_static(s)).collect()
     }
 
+    fn set_enabled_decoders(&mut self, formats: Vec<Txt>) {
+        self.image_cache.set_enabled_decoders(formats)
+    }
+
+    fn set_memory_pressure_thresholds(&mut self, thresholds: MemoryPressureThresholds) {
+        self.memory_pressure_thresholds = thresholds;
+    }
+
     fn add_image(&mut self, request: ImageRequest<IpcBytes>) -> ImageId {
         self.image_cache.add(request)
     }
@@ -1765,12 +1901,28 @@ impl Api for App {
     }
 
     fn encode_image(&mut self, id: ImageId, format: Txt) {
-        self.image_cache.encode(id, format)
+        self.image_cache
+            .encode(id, ImageEncodeId::INVALID, format, EncodeOptions::default())
+    }
+
+    fn encode_image_opts(&mut self, id: ImageId, format: Txt, opts: EncodeOptions) -> ImageEncodeId {
+        let r_id = self.image_encode_id_gen.incr();
+        self.image_cache.encode(id, r_id, format, opts);
+        r_id
+    }
+
+    fn encode_image_streaming(&mut self, id: ImageId, format: Txt) -> IpcBytesReceiver {
+        self.image_cache.encode_streaming(id, format, EncodeOptions::default())
     }
 
     fn use_image(&mut self, id: WindowId, image_id: ImageId) -> ImageTextureId {
         if let Some(img) = self.image_cache.get(image_id) {
-            with_window_or_surface!(self, id, |w| w.use_image(img), || ImageTextureId::INVALID)
+            let (texture_id, is_new) =
+                with_window_or_surface!(self, id, |w| w.use_image(image_id, img), || (ImageTextureId::INVALID, false));
+            if is_new {
+                self.image_cache.retain(image_id);
+            }
+            texture_id
         } else {
             ImageTextureId::INVALID
         }
@@ -1778,12 +1930,22 @@ impl Api for App {
 
     fn update_image_use(&mut self, id: WindowId, texture_id: ImageTextureId, image_id: ImageId) {
         if let Some(img) = self.image_cache.get(image_id) {
-            with_window_or_surface!(self, id, |w| w.update_image(texture_id, img), || ())
+            let prev = with_window_or_surface!(self, id, |w| w.update_image(texture_id, image_id, img), || None);
+            if let Some(prev) = prev {
+                self.image_cache.retain(image_id);
+                self.image_cache.forget(prev);
+            }
         }
     }
 
     fn delete_image_use(&mut self, id: WindowId, texture_id: ImageTextureId) {
-        with_window_or_surface!(self, id, |w| w.delete_image(texture_id), || ())
+        if let Some(image_id) = with_window_or_surface!(self, id, |w| w.delete_image(texture_id), || None) {
+            self.image_cache.forget(image_id);
+        }
+    }
+
+    fn image_ref_count(&mut self, id: ImageId) -> usize {
+        self.image_cache.ref_count(id)
     }
 
     fn add_font_face(&mut self, id: WindowId, bytes: IpcBytes, index: u32) -> FontFaceId {
@@ -1825,6 +1987,10 @@ impl Api for App {
         })
     }
 
+    fn frame_image_hash(&mut self, id: WindowId) -> (FrameId, u64) {
+        with_window_or_surface!(self, id, |w| w.frame_image_hash(), || (FrameId::INVALID, 0))
+    }
+
     fn render(&mut self, id: WindowId, frame: FrameRequest) {
         with_window_or_surface!(self, id, |w| w.render(frame), || ())
     }
@@ -1833,6 +1999,11 @@ impl Api for App {
         with_window_or_surface!(self, id, |w| w.render_update(frame), || ())
     }
 
+    fn request_frame_presented(&mut self, id: WindowId, frame: FrameId) {
+        with_window_or_surface!(self, id, |w| w.request_frame_presented(frame), || ());
+        self.notify_frames_presented(id);
+    }
+
     fn access_update(&mut self, id: WindowId, update: access::AccessTreeUpdate) {
         if let Some(s) = self.windows.iter_mut().find(|s| s.id() == id) {
             s.access_update(update, &self.app_sender);
@@ -1911,7 +2082,7 @@ impl Api for App {
 
                 if let Some(img) = self.image_cache.get(id) {
                     let mut bmp = vec![];
-                    img.encode(::image::ImageFormat::Bmp, &mut bmp)
+                    img.encode(::image::ImageFormat::Bmp, EncodeOptions::default(), &mut bmp)
                         .map_err(|e| clipboard::ClipboardError::Other(formatx!("{e:?}")))?;
                     clipboard_win::set(clipboard_win::formats::Bitmap, bmp).map_err(util::clipboard_win_to_clip)
                 } else {
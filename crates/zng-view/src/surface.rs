@@ -5,13 +5,13 @@ use webrender::{
     api::{DocumentId, DynamicProperties, FontInstanceKey, FontKey, FontVariation, PipelineId},
     RenderApi, Renderer, Transaction,
 };
-use winit::event_loop::ActiveEventLoop;
+use winit::{event_loop::ActiveEventLoop, window::Icon};
 use zng_unit::{DipSize, DipToPx, Factor, Px, PxRect, Rgba};
 use zng_view_api::{
     api_extension::{ApiExtensionId, ApiExtensionPayload},
     font::{FontFaceId, FontId, FontOptions, FontVariationName},
     image::{ImageId, ImageLoadedData, ImageMaskMode, ImageTextureId},
-    window::{FrameCapture, FrameId, FrameRequest, FrameUpdateRequest, HeadlessRequest, RenderMode, WindowId},
+    window::{FrameCapture, FrameId, FrameRequest, FrameUpdateRequest, HeadlessRequest, RenderMode, RendererInfo, WindowId},
     ViewProcessGen,
 };
 
@@ -24,7 +24,7 @@ use crate::{
     gl::{GlContext, GlContextManager},
     image_cache::{Image, ImageCache, ImageUseMap, WrImageCache},
     px_wr::PxToWr as _,
-    util::{frame_render_reasons, frame_update_render_reasons, PxToWinit},
+    util::{frame_render_reasons, frame_update_render_reasons, FramePresentedRequests, PxToWinit},
     AppEventSender, FrameReadyMsg, WrNotifier,
 };
 
@@ -48,6 +48,7 @@ pub(crate) struct Surface {
 
     pending_frames: VecDeque<(FrameId, FrameCapture, Option<EnteredSpan>)>,
     rendered_frame_id: FrameId,
+    frame_presented_requests: FramePresentedRequests,
     resized: bool,
 }
 impl fmt::Debug for Surface {
@@ -158,10 +159,16 @@ impl Surface {
 
             pending_frames: VecDeque::new(),
             rendered_frame_id: FrameId::INVALID,
+            frame_presented_requests: FramePresentedRequests::default(),
             resized: true,
         }
     }
 
+    /// Graphics adapter and driver info.
+    pub fn renderer_info(&mut self) -> RendererInfo {
+        self.context.renderer_info()
+    }
+
     pub fn render_mode(&self) -> RenderMode {
         self.context.render_mode()
     }
@@ -185,16 +192,25 @@ impl Surface {
         }
     }
 
-    pub fn use_image(&mut self, image: &Image) -> ImageTextureId {
-        self.image_use.new_use(image, self.document_id, &mut self.api)
+    /// Returns the texture and if it is new, new textures hold a reference to the image.
+    pub fn use_image(&mut self, image_id: ImageId, image: &Image) -> (ImageTextureId, bool) {
+        self.image_use.new_use(image_id, image, self.document_id, &mut self.api)
     }
 
-    pub fn update_image(&mut self, texture_id: ImageTextureId, image: &Image) {
-        self.image_use.update_use(texture_id, image, self.document_id, &mut self.api);
+    /// Returns the previous image if it was replaced.
+    pub fn update_image(&mut self, texture_id: ImageTextureId, image_id: ImageId, image: &Image) -> Option<ImageId> {
+        self.image_use
+            .update_use(texture_id, image_id, image, self.document_id, &mut self.api)
     }
 
-    pub fn delete_image(&mut self, texture_id: ImageTextureId) {
-        self.image_use.delete(texture_id, self.document_id, &mut self.api);
+    /// Returns the image that was used by the texture.
+    pub fn delete_image(&mut self, texture_id: ImageTextureId) -> Option<ImageId> {
+        self.image_use.delete(texture_id, self.document_id, &mut self.api)
+    }
+
+    /// Images referenced by the renderer textures, one entry per texture.
+    pub fn used_images(&self) -> impl Iterator<Item = ImageId> + '_ {
+        self.image_use.used_images()
     }
 
     pub fn add_font_face(&mut self, font: Vec<u8>, index: u32) -> FontFaceId {
@@ -363,6 +379,7 @@ impl Surface {
             .pop_front()
             .unwrap_or((self.rendered_frame_id, FrameCapture::None, None));
         self.rendered_frame_id = frame_id;
+        self.frame_presented_requests.presented(frame_id);
 
         let mut captured_data = None;
 
@@ -423,6 +440,39 @@ impl Surface {
         )
     }
 
+    /// Register a `request_frame_presented`, surfaces present on render.
+    pub fn request_frame_presented(&mut self, frame: FrameId) {
+        self.frame_presented_requests.request(frame, self.rendered_frame_id);
+    }
+
+    /// Frames that where requested and are now rendered.
+    pub fn take_frames_presented(&mut self) -> Vec<FrameId> {
+        self.frame_presented_requests.take_ready()
+    }
+
+    /// Bytes allocated in the GPU by the renderer.
+    pub fn gpu_memory(&self) -> usize {
+        crate::util::gpu_memory(&self.renderer.as_ref().unwrap().report_memory(std::ptr::null_mut()))
+    }
+
+    pub fn frame_image_hash(&mut self) -> (FrameId, u64) {
+        if self.rendered_frame_id == FrameId::INVALID {
+            return (FrameId::INVALID, 0);
+        }
+        self.context.make_current();
+        let rect = PxRect::from_size(self.size.to_px(self.scale_factor));
+        (self.rendered_frame_id, ImageCache::frame_pixels_hash(&**self.context.gl(), rect))
+    }
+
+    /// Generate a window icon from the last rendered frame.
+    pub fn frame_icon(&mut self) -> Option<Icon> {
+        if self.rendered_frame_id == FrameId::INVALID {
+            return None;
+        }
+        self.context.make_current();
+        ImageCache::frame_icon(&**self.context.gl(), PxRect::from_size(self.size.to_px(self.scale_factor)))
+    }
+
     pub fn frame_image_rect(&mut self, images: &mut ImageCache, rect: PxRect, mask: Option<ImageMaskMode>) -> ImageId {
         let rect = PxRect::from_size(self.size.to_px(self.scale_factor)).intersection(&rect).unwrap();
         images.frame_image(&**self.context.gl(), rect, self.id, self.rendered_frame_id, self.scale_factor, mask)
@@ -14,7 +14,7 @@ use zng_unit::*;
 use zng_view_api::access::AccessNodeId;
 use zng_view_api::clipboard as clipboard_api;
 use zng_view_api::keyboard::{KeyLocation, NativeKeyCode};
-use zng_view_api::window::{FrameCapture, FrameRequest, FrameUpdateRequest, ResizeDirection, WindowButton};
+use zng_view_api::window::{FrameCapture, FrameId, FrameRequest, FrameUpdateRequest, ResizeDirection, WindowButton};
 use zng_view_api::{
     keyboard::{Key, KeyCode, KeyState},
     mouse::{ButtonState, MouseButton, MouseScrollDelta},
@@ -1637,3 +1637,177 @@ impl<'l> Drop for WinitEventLoopGuard<'l> {
         }
     }
 }
+
+/// Pending `Api::request_frame_presented` requests of a window or surface.
+#[derive(Default)]
+pub(crate) struct FramePresentedRequests {
+    pending: Vec<FrameId>,
+    ready: Vec<FrameId>,
+}
+impl FramePresentedRequests {
+    /// Register request, `presented` is the last presented frame.
+    ///
+    /// Repeated requests for the same frame are coalesced into a single notification.
+    pub fn request(&mut self, frame: FrameId, presented: FrameId) {
+        if self.pending.contains(&frame) || self.ready.contains(&frame) {
+            return;
+        }
+        if Self::is_presented(frame, presented) {
+            self.ready.push(frame);
+        } else {
+            self.pending.push(frame);
+        }
+    }
+
+    /// Move pending requests that are now presented to ready.
+    pub fn presented(&mut self, presented: FrameId) {
+        self.pending.retain(|&f| {
+            if Self::is_presented(f, presented) {
+                self.ready.push(f);
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    /// Take the frames that can be notified.
+    pub fn take_ready(&mut self) -> Vec<FrameId> {
+        std::mem::take(&mut self.ready)
+    }
+
+    fn is_presented(frame: FrameId, presented: FrameId) -> bool {
+        presented != FrameId::INVALID && presented.get() >= frame.get()
+    }
+}
+
+/// Sum of the GPU memory entries of the renderer memory report.
+pub(crate) fn gpu_memory(r: &wr::MemoryReport) -> usize {
+    r.gpu_cache_textures
+        + r.vertex_data_textures
+        + r.render_target_textures
+        + r.picture_tile_textures
+        + r.atlas_textures
+        + r.standalone_textures
+        + r.texture_cache_structures
+        + r.depth_target_textures
+        + r.texture_upload_pbos
+        + r.swap_chain
+        + r.render_texture_hosts
+        + r.upload_staging_textures
+}
+
+/// Clamp the `rect` to be fully inside the `monitor` area, both rectangles are in the same space.
+///
+/// If the `rect` is larger than the monitor it is resized to fit.
+pub(crate) fn clamp_rect_to_monitor(rect: DipRect, monitor: DipRect) -> DipRect {
+    let size = rect.size.min(monitor.size);
+    let origin = DipPoint::new(
+        rect.origin.x.min(monitor.max_x() - size.width).max(monitor.origin.x),
+        rect.origin.y.min(monitor.max_y() - size.height).max(monitor.origin.y),
+    );
+    DipRect::new(origin, size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: i32, y: i32, w: i32, h: i32) -> DipRect {
+        DipRect::new(DipPoint::new(Dip::new(x), Dip::new(y)), DipSize::new(Dip::new(w), Dip::new(h)))
+    }
+
+    fn frame(epoch: u32) -> FrameId {
+        (0..epoch).fold(FrameId::first(), |f, _| f.next())
+    }
+
+    #[test]
+    fn frame_presented_request_already_presented() {
+        let mut r = FramePresentedRequests::default();
+        r.request(frame(1), frame(2));
+        r.request(frame(2), frame(2));
+        assert_eq!(r.take_ready(), vec![frame(1), frame(2)]);
+        assert!(r.take_ready().is_empty());
+    }
+
+    #[test]
+    fn frame_presented_request_pending() {
+        let mut r = FramePresentedRequests::default();
+        r.request(frame(0), FrameId::INVALID);
+        r.request(frame(3), frame(2));
+        assert!(r.take_ready().is_empty());
+
+        r.presented(frame(2));
+        assert_eq!(r.take_ready(), vec![frame(0)]);
+
+        r.presented(frame(3));
+        assert_eq!(r.take_ready(), vec![frame(3)]);
+
+        r.presented(frame(4));
+        assert!(r.take_ready().is_empty());
+    }
+
+    #[test]
+    fn frame_presented_request_coalesce() {
+        let mut r = FramePresentedRequests::default();
+        r.request(frame(3), frame(1));
+        r.request(frame(3), frame(1));
+        r.request(frame(2), frame(1));
+        r.request(frame(1), frame(1));
+        r.request(frame(1), frame(1));
+
+        r.presented(frame(3));
+        assert_eq!(r.take_ready(), vec![frame(1), frame(3), frame(2)]);
+    }
+
+    #[test]
+    fn frame_presented_request_order() {
+        let mut r = FramePresentedRequests::default();
+        r.request(frame(5), frame(1));
+        r.request(frame(3), frame(1));
+        r.request(frame(4), frame(1));
+        r.request(frame(2), frame(1));
+
+        r.presented(frame(3));
+        assert_eq!(r.take_ready(), vec![frame(3), frame(2)]);
+
+        r.presented(frame(5));
+        assert_eq!(r.take_ready(), vec![frame(5), frame(4)]);
+    }
+
+    #[test]
+    fn clamp_rect_inside() {
+        let monitor = rect(1920, 0, 1920, 1080);
+        assert_eq!(clamp_rect_to_monitor(rect(2000, 100, 800, 600), monitor), rect(2000, 100, 800, 600));
+    }
+
+    #[test]
+    fn clamp_rect_fully_off_screen() {
+        let monitor = rect(1920, 0, 1920, 1080);
+        assert_eq!(
+            clamp_rect_to_monitor(rect(5000, 3000, 800, 600), monitor),
+            rect(3040, 480, 800, 600)
+        );
+        assert_eq!(
+            clamp_rect_to_monitor(rect(-2000, -2000, 800, 600), monitor),
+            rect(1920, 0, 800, 600)
+        );
+    }
+
+    #[test]
+    fn clamp_rect_partially_visible() {
+        let monitor = rect(1920, 0, 1920, 1080);
+        assert_eq!(clamp_rect_to_monitor(rect(3500, 900, 800, 600), monitor), rect(3040, 480, 800, 600));
+        assert_eq!(clamp_rect_to_monitor(rect(1800, -100, 800, 600), monitor), rect(1920, 0, 800, 600));
+    }
+
+    #[test]
+    fn clamp_rect_larger_than_monitor() {
+        let monitor = rect(1920, 0, 1920, 1080);
+        assert_eq!(clamp_rect_to_monitor(rect(1800, 100, 3000, 2000), monitor), monitor);
+        assert_eq!(
+            clamp_rect_to_monitor(rect(2000, 100, 3000, 600), monitor),
+            rect(1920, 100, 1920, 600)
+        );
+    }
+}
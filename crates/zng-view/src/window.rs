@@ -22,14 +22,15 @@ use winit::{
     window::{CustomCursor, Fullscreen, Icon, Window as GWindow, WindowAttributes},
 };
 use zng_txt::{ToTxt, Txt};
-use zng_unit::{DipPoint, DipRect, DipSize, DipToPx, Factor, Px, PxPoint, PxRect, PxToDip, PxVector, Rgba};
+use zng_unit::{Dip, DipPoint, DipRect, DipSize, DipToPx, Factor, Px, PxPoint, PxRect, PxToDip, PxVector, Rgba};
 use zng_view_api::{
     api_extension::{ApiExtensionId, ApiExtensionPayload},
+    config::ColorScheme,
     font::{FontFaceId, FontId, FontOptions, FontVariationName},
     image::{ImageId, ImageLoadedData, ImageMaskMode, ImageTextureId},
     window::{
-        CursorIcon, FocusIndicator, FrameCapture, FrameId, FrameRequest, FrameUpdateRequest, RenderMode, ResizeDirection, VideoMode,
-        WindowButton, WindowId, WindowRequest, WindowState, WindowStateAll,
+        CursorIcon, FocusIndicator, FrameCapture, FrameId, FrameRequest, FrameUpdateRequest, RenderMode, RendererInfo, ResizeDirection,
+        VideoMode, WindowButton, WindowId, WindowLevel, WindowRequest, WindowState, WindowStateAll,
     },
     DeviceId, Event, ViewProcessGen,
 };
@@ -50,8 +51,8 @@ use crate::{
     image_cache::{Image, ImageCache, ImageUseMap, WrImageCache},
     px_wr::PxToWr as _,
     util::{
-        frame_render_reasons, frame_update_render_reasons, CursorToWinit, DipToWinit, PxToWinit, ResizeDirectionToWinit as _,
-        WindowButtonsToWinit as _, WinitToDip, WinitToPx,
+        frame_render_reasons, frame_update_render_reasons, glutin_video_mode_to_video_mode, CursorToWinit, DipToWinit,
+        FramePresentedRequests, PxToWinit, ResizeDirectionToWinit as _, WindowButtonsToWinit as _, WinitToDip, WinitToPx,
     },
     AppEvent, AppEventSender, FrameReadyMsg, WrNotifier,
 };
@@ -78,11 +79,14 @@ pub(crate) struct Window {
 
     pending_frames: VecDeque<(FrameId, FrameCapture, Option<EnteredSpan>)>,
     rendered_frame_id: FrameId,
+    presented_frame_id: FrameId,
+    frame_presented_requests: FramePresentedRequests,
     kiosk: bool,
 
     resized: bool,
 
     video_mode: VideoMode,
+    applied_video_mode: Option<VideoMode>,
 
     state: WindowStateAll,
 
@@ -92,7 +96,7 @@ pub(crate) struct Window {
     prev_monitor: Option<MonitorHandle>,
 
     visible: bool,
-    is_always_on_top: bool,
+    level: WindowLevel,
     waiting_first_frame: bool,
     steal_init_focus: bool,
     init_focus_request: Option<FocusIndicator>,
@@ -100,6 +104,8 @@ pub(crate) struct Window {
     taskbar_visible: bool,
 
     movable: bool,
+    aspect_ratio: Option<f32>,
+    clamp_to_monitor: bool,
 
     cursor_pos: DipPoint,
     cursor_device: DeviceId,
@@ -436,6 +442,7 @@ impl Window {
             renderer_exts,
             external_images,
             video_mode: cfg.video_mode,
+            applied_video_mode: None,
             display_list_cache: DisplayListCache::new(pipeline_id, api.get_namespace_id()),
             api,
             document_id,
@@ -445,11 +452,15 @@ impl Window {
             steal_init_focus: cfg.focus,
             init_focus_request: cfg.focus_indicator,
             visible: cfg.visible,
-            is_always_on_top: false,
+            level: WindowLevel::Normal,
             taskbar_visible: true,
             movable: cfg.movable,
+            aspect_ratio: cfg.aspect_ratio.filter(|r| r.is_finite() && *r > 0.0),
+            clamp_to_monitor: cfg.clamp_to_monitor,
             pending_frames: VecDeque::new(),
             rendered_frame_id: FrameId::INVALID,
+            presented_frame_id: FrameId::INVALID,
+            frame_presented_requests: FramePresentedRequests::default(),
             cursor_pos: DipPoint::zero(),
             touch_pos: vec![],
             cursor_device: DeviceId::INVALID,
@@ -481,8 +492,8 @@ impl Window {
             win.set_inner_position(win.state.restore_rect.origin);
         }
 
-        if cfg.always_on_top {
-            win.set_always_on_top(true);
+        if cfg.level != WindowLevel::Normal {
+            win.set_window_level(cfg.level);
         }
 
         win.cursor = cfg.cursor;
@@ -664,12 +675,27 @@ impl Window {
     }
 
     pub fn set_always_on_top(&mut self, always_on_top: bool) {
-        self.window.set_window_level(if always_on_top {
-            winit::window::WindowLevel::AlwaysOnTop
+        self.set_window_level(if always_on_top {
+            WindowLevel::AlwaysOnTop
         } else {
-            winit::window::WindowLevel::Normal
+            WindowLevel::Normal
         });
-        self.is_always_on_top = always_on_top;
+    }
+
+    pub fn set_window_level(&mut self, level: WindowLevel) {
+        self.window.set_window_level(match level {
+            WindowLevel::Normal => winit::window::WindowLevel::Normal,
+            WindowLevel::AlwaysOnBottom => winit::window::WindowLevel::AlwaysOnBottom,
+            WindowLevel::AlwaysOnTop => winit::window::WindowLevel::AlwaysOnTop,
+        });
+        self.level = level;
+    }
+
+    pub fn set_preferred_theme(&mut self, theme: Option<ColorScheme>) {
+        self.window.set_theme(theme.map(|t| match t {
+            ColorScheme::Light => winit::window::Theme::Light,
+            ColorScheme::Dark => winit::window::Theme::Dark,
+        }));
     }
 
     pub fn set_movable(&mut self, movable: bool) {
@@ -680,11 +706,68 @@ impl Window {
         self.window.set_resizable(resizable)
     }
 
+    pub fn set_aspect_ratio(&mut self, ratio: Option<f32>) {
+        self.aspect_ratio = ratio.filter(|r| r.is_finite() && *r > 0.0);
+
+        if self.aspect_ratio.is_some() && self.state.state == WindowState::Normal {
+            let size = self.window.inner_size().to_px().to_dip(self.scale_factor());
+            let new_size = self.constrain_aspect_ratio(size);
+            if new_size != size {
+                let _ = self.window.request_inner_size(new_size.to_winit());
+            }
+        }
+    }
+
+    pub fn set_clamp_to_monitor(&mut self, clamp: bool) {
+        self.clamp_to_monitor = clamp;
+
+        if clamp && self.visible && self.state.state == WindowState::Normal {
+            let rect = self.clamp_restore_rect(self.state.restore_rect);
+            if rect != self.state.restore_rect {
+                self.apply_state(self.state.clone(), false);
+            }
+        }
+    }
+
+    /// Clamp the `rect` to the current monitor if `clamp_to_monitor` is enabled.
+    ///
+    /// The `rect` origin is relative to the monitor, like `restore_rect`. Winit does not provide the monitor
+    /// work area, so the full monitor area is used.
+    fn clamp_restore_rect(&self, rect: DipRect) -> DipRect {
+        if !self.clamp_to_monitor {
+            return rect;
+        }
+        let monitor = match self.window.current_monitor() {
+            Some(m) => m,
+            None => return rect,
+        };
+        let scale_factor = Factor(monitor.scale_factor() as f32);
+        let monitor_rect = PxRect::new(monitor.position().to_px(), monitor.size().to_px()).to_dip(scale_factor);
+        let rect = rect.translate(monitor_rect.origin.to_vector());
+        crate::util::clamp_rect_to_monitor(rect, monitor_rect).translate(-monitor_rect.origin.to_vector())
+    }
+
+    /// Adjust the dimension that changed the least from the previous size to maintain the aspect ratio.
+    fn constrain_aspect_ratio(&self, size: DipSize) -> DipSize {
+        match self.aspect_ratio {
+            Some(ratio) => {
+                let width_change = (size.width - self.prev_size.width).abs();
+                let height_change = (size.height - self.prev_size.height).abs();
+                if width_change >= height_change {
+                    DipSize::new(size.width, Dip::new_f32(size.width.to_f32() / ratio))
+                } else {
+                    DipSize::new(Dip::new_f32(size.height.to_f32() * ratio), size.height)
+                }
+            }
+            None => size,
+        }
+    }
+
     #[cfg(windows)]
     pub fn bring_to_top(&mut self) {
         use windows_sys::Win32::UI::WindowsAndMessaging::*;
 
-        if !self.is_always_on_top {
+        if self.level != WindowLevel::AlwaysOnTop {
             let hwnd = crate::util::winit_to_hwnd(&self.window);
 
             unsafe {
@@ -703,9 +786,10 @@ impl Window {
 
     #[cfg(not(windows))]
     pub fn bring_to_top(&mut self) {
-        if !self.is_always_on_top {
-            self.set_always_on_top(true);
-            self.set_always_on_top(false);
+        if self.level != WindowLevel::AlwaysOnTop {
+            let level = self.level;
+            self.set_window_level(WindowLevel::AlwaysOnTop);
+            self.set_window_level(level);
         }
     }
 
@@ -757,7 +841,19 @@ impl Window {
             return None;
         }
 
-        let new_size = self.window.inner_size().to_px().to_dip(self.scale_factor());
+        let mut new_size = self.window.inner_size().to_px().to_dip(self.scale_factor());
+
+        if self.aspect_ratio.is_some() && self.state.state == WindowState::Normal {
+            let constrained_size = self.constrain_aspect_ratio(new_size);
+            if constrained_size != new_size {
+                if let Some(immediate_new_size) = self.window.request_inner_size(constrained_size.to_winit()) {
+                    new_size = immediate_new_size.to_px().to_dip(self.scale_factor());
+                } else {
+                    new_size = constrained_size;
+                }
+            }
+        }
+
         if self.prev_size != new_size {
             #[cfg(windows)]
             if matches!(self.state.state, WindowState::Maximized | WindowState::Fullscreen)
@@ -1041,8 +1137,9 @@ impl Window {
         } else if new_state.state == WindowState::Normal && self.state.state != WindowState::Normal {
             new_state.restore_rect = self.state.restore_rect;
 
-            self.set_inner_position(new_state.restore_rect.origin);
-            let new_size = new_state.restore_rect.size.to_winit();
+            let rect = self.clamp_restore_rect(new_state.restore_rect);
+            self.set_inner_position(rect.origin);
+            let new_size = rect.size.to_winit();
             if let Some(immediate_new_size) = self.window.request_inner_size(new_size) {
                 if immediate_new_size == new_size.to_physical(self.window.scale_factor()) {
                     // size changed immediately, winit says: "resize event in such case may not be generated"
@@ -1094,6 +1191,20 @@ impl Window {
         })
     }
 
+    /// Probe the actual exclusive video mode, returns `Some(new_mode)` if it changed.
+    pub fn video_mode_change(&mut self) -> Option<Option<VideoMode>> {
+        let mode = match self.window.fullscreen() {
+            Some(Fullscreen::Exclusive(m)) => Some(glutin_video_mode_to_video_mode(m)),
+            _ => None,
+        };
+        if self.applied_video_mode != mode {
+            self.applied_video_mode = mode;
+            Some(mode)
+        } else {
+            None
+        }
+    }
+
     pub fn set_video_mode(&mut self, mode: VideoMode) {
         self.video_mode = mode;
         if let WindowState::Exclusive = self.state.state {
@@ -1274,21 +1385,28 @@ impl Window {
         self.state = new_state;
 
         if self.state.state == WindowState::Normal {
-            let _ = self.window.request_inner_size(self.state.restore_rect.size.to_winit());
+            if self.clamp_to_monitor {
+                // the requested `restore_rect` is kept in `state`, so a clamped rect is notified by `state_change`.
+                let rect = self.clamp_restore_rect(self.state.restore_rect);
+                let _ = self.window.request_inner_size(rect.size.to_winit());
+                self.set_inner_position(rect.origin);
+            } else {
+                let _ = self.window.request_inner_size(self.state.restore_rect.size.to_winit());
 
-            let outer_offset = match (self.window.outer_position(), self.window.inner_position()) {
-                (Ok(o), Ok(i)) => (i.x - o.x, i.y - o.y),
-                _ => (0, 0),
-            };
-            let mut origin = self
-                .state
-                .restore_rect
-                .origin
-                .to_winit()
-                .to_physical::<i32>(self.window.scale_factor());
-            origin.x -= outer_offset.0;
-            origin.y -= outer_offset.1;
-            self.window.set_outer_position(origin);
+                let outer_offset = match (self.window.outer_position(), self.window.inner_position()) {
+                    (Ok(o), Ok(i)) => (i.x - o.x, i.y - o.y),
+                    _ => (0, 0),
+                };
+                let mut origin = self
+                    .state
+                    .restore_rect
+                    .origin
+                    .to_winit()
+                    .to_physical::<i32>(self.window.scale_factor());
+                origin.x -= outer_offset.0;
+                origin.y -= outer_offset.1;
+                self.window.set_outer_position(origin);
+            }
 
             self.window.set_min_inner_size(Some(self.state.min_size.to_winit()));
             self.window.set_max_inner_size(Some(self.state.max_size.to_winit()));
@@ -1320,16 +1438,25 @@ impl Window {
         }
     }
 
-    pub fn use_image(&mut self, image: &Image) -> ImageTextureId {
-        self.image_use.new_use(image, self.document_id, &mut self.api)
+    /// Returns the texture and if it is new, new textures hold a reference to the image.
+    pub fn use_image(&mut self, image_id: ImageId, image: &Image) -> (ImageTextureId, bool) {
+        self.image_use.new_use(image_id, image, self.document_id, &mut self.api)
     }
 
-    pub fn update_image(&mut self, texture_id: ImageTextureId, image: &Image) {
-        self.image_use.update_use(texture_id, image, self.document_id, &mut self.api);
+    /// Returns the previous image if it was replaced.
+    pub fn update_image(&mut self, texture_id: ImageTextureId, image_id: ImageId, image: &Image) -> Option<ImageId> {
+        self.image_use
+            .update_use(texture_id, image_id, image, self.document_id, &mut self.api)
     }
 
-    pub fn delete_image(&mut self, texture_id: ImageTextureId) {
-        self.image_use.delete(texture_id, self.document_id, &mut self.api);
+    /// Returns the image that was used by the texture.
+    pub fn delete_image(&mut self, texture_id: ImageTextureId) -> Option<ImageId> {
+        self.image_use.delete(texture_id, self.document_id, &mut self.api)
+    }
+
+    /// Images referenced by the renderer textures, one entry per texture.
+    pub fn used_images(&self) -> impl Iterator<Item = ImageId> + '_ {
+        self.image_use.used_images()
     }
 
     pub fn add_font_face(&mut self, font: Vec<u8>, index: u32) -> FontFaceId {
@@ -1605,6 +1732,24 @@ impl Window {
 
         self.window.pre_present_notify();
         self.context.swap_buffers();
+
+        self.presented_frame_id = self.rendered_frame_id;
+        self.frame_presented_requests.presented(self.presented_frame_id);
+    }
+
+    /// Register a `request_frame_presented`.
+    pub fn request_frame_presented(&mut self, frame: FrameId) {
+        self.frame_presented_requests.request(frame, self.presented_frame_id);
+    }
+
+    /// Frames that where requested and are now presented.
+    pub fn take_frames_presented(&mut self) -> Vec<FrameId> {
+        self.frame_presented_requests.take_ready()
+    }
+
+    /// Bytes allocated in the GPU by the renderer.
+    pub fn gpu_memory(&self) -> usize {
+        crate::util::gpu_memory(&self.renderer.as_ref().unwrap().report_memory(std::ptr::null_mut()))
     }
 
     pub fn is_rendering_frame(&self) -> bool {
@@ -1648,6 +1793,17 @@ impl Window {
         images.frame_image(&**self.context.gl(), rect, self.id, self.rendered_frame_id, scale_factor, mask)
     }
 
+    pub fn frame_image_hash(&mut self) -> (FrameId, u64) {
+        if self.rendered_frame_id == FrameId::INVALID {
+            return (FrameId::INVALID, 0);
+        }
+        if !self.context.is_software() {
+            self.redraw(); // refresh back buffer
+        }
+        let rect = PxRect::from_size(self.window.inner_size().to_px());
+        (self.rendered_frame_id, ImageCache::frame_pixels_hash(&**self.context.gl(), rect))
+    }
+
     /// (global_position, monitor_position)
     pub fn inner_position(&self) -> (PxPoint, DipPoint) {
         let global_pos = self.window.inner_position().unwrap_or_default().to_px();
@@ -1668,6 +1824,11 @@ impl Window {
         Factor(self.window.scale_factor() as f32)
     }
 
+    /// Graphics adapter and driver info.
+    pub fn renderer_info(&mut self) -> RendererInfo {
+        self.context.renderer_info()
+    }
+
     /// Window actual render mode.
     pub fn render_mode(&self) -> RenderMode {
         self.render_mode
@@ -10,7 +10,8 @@
 #![warn(missing_docs)]
 
 use parking_lot::Mutex;
-use std::{any::TypeId, sync::Arc};
+use std::{any::TypeId, sync::Arc, time::Duration};
+use zng_var::animation::AnimationHandle;
 
 use zng_wgt::prelude::*;
 
@@ -21,7 +22,9 @@ use zng_wgt::prelude::*;
 pub struct DataViewArgs<D: VarValue> {
     data: BoxedVar<D>,
     replace: Arc<Mutex<Option<BoxedUiNode>>>,
+    pool: Arc<Mutex<ViewPool>>,
     is_nil: bool,
+    is_first: bool,
 }
 impl<D: VarValue> DataViewArgs<D> {
     /// Reference the data variable.
@@ -50,13 +53,56 @@ impl<D: VarValue> DataViewArgs<D> {
         self.is_nil
     }
 
+    /// If the handler is called on init.
+    ///
+    /// Is `true` only in the first call after the widget inits, subsequent calls caused by [`data`] updates are `false`.
+    /// Note that [`get_new`] is also `Some(_)` on init, this flag can be used to tell the initial build from an update.
+    ///
+    /// [`data`]: Self::data
+    /// [`get_new`]: Self::get_new
+    pub fn is_first(&self) -> bool {
+        self.is_first
+    }
+
     /// Replace the child node.
     ///
-    /// If set the current child node will be deinited and dropped.
+    /// If set the current child node will be deinited and dropped, or stashed if it was set by [`set_view_pooled`].
+    ///
+    /// [`set_view_pooled`]: Self::set_view_pooled
     pub fn set_view(&self, new_child: impl UiNode) {
+        self.pool.lock().next = None;
         *self.replace.lock() = Some(new_child.boxed());
     }
 
+    /// Replace the child node with a pooled view built by `build`.
+    ///
+    /// If the current view was built by the same `build` closure it is retained. If a view built by the closure was previously
+    /// replaced it is reused, otherwise `build` is called to create the view. Views set by this method are deinited and stashed
+    /// when replaced, so that swapping between a small set of views does not rebuild the views every time. All stashed
+    /// views are dropped when the widget deinits.
+    ///
+    /// Note that the views are keyed by the type of the `build` closure, every closure expression has a distinct type so views
+    /// built by different closures are never mixed, even if they return the same node type or are boxed by `dyn_node`. Views built
+    /// by the same closure expression must be equivalent, they should bind to the [`data`] variable, as the reused view is not rebuilt
+    /// for the new data.
+    ///
+    /// [`data`]: Self::data
+    pub fn set_view_pooled<N: UiNode>(&self, build: impl FnOnce() -> N + 'static) {
+        let key = type_id_of_val(&build);
+        let mut pool = self.pool.lock();
+        if pool.current == Some(key) {
+            pool.next = None;
+            *self.replace.lock() = None;
+            return;
+        }
+        let view = match pool.views.iter().position(|(k, _)| *k == key) {
+            Some(i) => pool.views.swap_remove(i).1,
+            None => build().boxed(),
+        };
+        pool.next = Some(key);
+        *self.replace.lock() = Some(view);
+    }
+
     /// Set the view to [`NilUiNode`].
     ///
     /// [`NilUiNode`]: zng_wgt::prelude::NilUiNode
@@ -65,6 +111,63 @@ impl<D: VarValue> DataViewArgs<D> {
     }
 }
 
+fn type_id_of_val<T: 'static>(_: &T) -> TypeId {
+    TypeId::of::<T>()
+}
+
+/// Arguments for [`view_error_fn`].
+///
+/// [`view_error_fn`]: fn@view_error_fn
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataViewErrorArgs {
+    /// Panic message.
+    pub error: Txt,
+}
+
+context_var! {
+    /// Widget function for the content shown when the view build panics.
+    pub static VIEW_ERROR_FN_VAR: WidgetFn<DataViewErrorArgs> = WidgetFn::nil();
+
+    /// Duration of the crossfade transition when the view is replaced.
+    pub static VIEW_TRANSITION_VAR: Duration = Duration::ZERO;
+}
+
+#[derive(Default)]
+struct ViewPool {
+    // key of the current child, if it was pooled.
+    current: Option<TypeId>,
+    // key of the `replace` child, if it is pooled.
+    next: Option<TypeId>,
+    // deinited views.
+    views: Vec<(TypeId, BoxedUiNode)>,
+}
+impl ViewPool {
+    /// Stash `old_child` if it is pooled and update the current key.
+    fn replace(&mut self, old_child: BoxedUiNode) {
+        let key = self.swap_current();
+        self.stash(key, old_child);
+    }
+
+    /// Update the current key, returns the key of the old child.
+    fn swap_current(&mut self) -> Option<TypeId> {
+        std::mem::replace(&mut self.current, self.next.take())
+    }
+
+    /// Stash the deinited `old_child` if it is pooled, replaces the view already stashed for the same key.
+    ///
+    /// A view can be stashed for a key that is already in the pool when the view is requested again while it is
+    /// the outgoing view of a transition, in this case a new view is built and the outgoing view is stashed later.
+    fn stash(&mut self, key: Option<TypeId>, old_child: BoxedUiNode) {
+        if let Some(key) = key {
+            if let Some((_, view)) = self.views.iter_mut().find(|(k, _)| *k == key) {
+                *view = old_child;
+            } else {
+                self.views.push((key, old_child));
+            }
+        }
+    }
+}
+
 /// Dynamically presents a data variable.
 ///
 /// # Shorthand
@@ -123,53 +226,570 @@ impl DataView {
 /// The `update` widget handler is used to generate the view from the `data`, it is called on init and
 /// every time `data` or `update` are new. The view is set by calling [`DataViewArgs::set_view`] in the widget function
 /// args, note that the data variable is available in [`DataViewArgs::data`], a good view will bind to the variable
-/// to support some changes, only replacing the view for major changes.
+/// to support some changes, only replacing the view for major changes. Views that are swapped often can be
+/// recycled using [`DataViewArgs::set_view_pooled`].
+///
+/// If [`view_error_fn`] is set, panics in the `update` handler or in the new view init are caught and the view
+/// is replaced with the error view. If [`view_transition`] is set the view replacement crossfades.
 ///
 /// [`DataView!`]: struct@DataView
+/// [`view_error_fn`]: fn@view_error_fn
+/// [`view_transition`]: fn@view_transition
 #[property(CHILD, widget_impl(DataView))]
 pub fn view<D: VarValue>(child: impl UiNode, data: impl IntoVar<D>, update: impl WidgetHandler<DataViewArgs<D>>) -> impl UiNode {
     let data = data.into_var().boxed();
     let mut update = update.cfg_boxed();
     let replace = Arc::new(Mutex::new(None));
+    let pool = Arc::new(Mutex::new(ViewPool::default()));
+
+    let mut outgoing: Option<ViewTransition> = None;
+    let progress = var(1.fct());
+    let opacity_key = FrameValueKey::new_unique();
 
     match_node(child.boxed(), move |c, op| match op {
         UiNodeOp::Init => {
-            WIDGET.sub_var(&data);
-            update.event(&DataViewArgs {
-                data: data.clone(),
-                replace: replace.clone(),
-                is_nil: true,
+            WIDGET.sub_var(&data).sub_var(&progress);
+            let r = catch_view_panic(|| {
+                update.event(&DataViewArgs {
+                    data: data.clone(),
+                    replace: replace.clone(),
+                    pool: pool.clone(),
+                    is_nil: true,
+                    is_first: true,
+                });
             });
-            if let Some(child) = replace.lock().take() {
-                *c.child() = child;
+            if let Err(e) = r {
+                replace_with_error(c.child(), &replace, &pool, e);
+            } else if let Some(child) = replace.lock().take() {
+                let old_child = std::mem::replace(c.child(), child);
+                pool.lock().replace(old_child);
             }
+            init_view(c.child(), &replace, &pool);
+            c.delegated();
         }
         UiNodeOp::Deinit => {
             c.deinit();
+            if let Some(mut t) = outgoing.take() {
+                t.view.deinit();
+            }
             *c.child() = NilUiNode.boxed();
+            *pool.lock() = ViewPool::default();
         }
-        UiNodeOp::Update { .. } => {
-            if data.is_new() {
-                update.event(&DataViewArgs {
-                    data: data.clone(),
-                    replace: replace.clone(),
-                    is_nil: c.child().actual_type_id() == TypeId::of::<NilUiNode>(),
-                });
+        UiNodeOp::Update { updates } => {
+            if let Some(t) = &mut outgoing {
+                t.view.update(updates);
+                if progress.is_new() {
+                    if progress.get() >= 1.fct() {
+                        end_transition(&mut outgoing, &pool);
+                        WIDGET.update_info().layout().render();
+                    } else {
+                        WIDGET.render_update();
+                    }
+                }
             }
 
-            update.update();
+            let r = catch_view_panic(|| {
+                if data.is_new() {
+                    update.event(&DataViewArgs {
+                        data: data.clone(),
+                        replace: replace.clone(),
+                        pool: pool.clone(),
+                        is_nil: c.child().actual_type_id() == TypeId::of::<NilUiNode>(),
+                        is_first: false,
+                    });
+                }
 
-            if let Some(child) = replace.lock().take() {
+                update.update();
+            });
+            // take now, the lock must not be held during `init_view`, it may replace with the error view.
+            let new_child = replace.lock().take();
+            if let Err(e) = r {
+                end_transition(&mut outgoing, &pool);
+                c.child().deinit();
+                replace_with_error(c.child(), &replace, &pool, e);
+                c.child().init();
+                c.delegated();
+                WIDGET.update_info().layout().render();
+            } else if let Some(child) = new_child {
                 // skip update if nil -> nil, otherwise updates
                 if c.child().actual_type_id() != TypeId::of::<NilUiNode>() || child.actual_type_id() != TypeId::of::<NilUiNode>() {
-                    c.child().deinit();
-                    *c.child() = child;
-                    c.child().init();
+                    end_transition(&mut outgoing, &pool);
+
+                    let duration = VIEW_TRANSITION_VAR.get();
+                    if duration > Duration::ZERO && c.child().actual_type_id() != TypeId::of::<NilUiNode>() {
+                        // keep the old child alive for the transition.
+                        let view = std::mem::replace(c.child(), child);
+                        outgoing = Some(ViewTransition {
+                            key: pool.lock().swap_current(),
+                            view,
+                            _animation: progress.set_ease(0.fct(), 1.fct(), duration, zng_var::animation::easing::linear),
+                        });
+                    } else {
+                        c.child().deinit();
+                        let old_child = std::mem::replace(c.child(), child);
+                        pool.lock().replace(old_child);
+                    }
+                    init_view(c.child(), &replace, &pool);
                     c.delegated();
                     WIDGET.update_info().layout().render();
                 }
             }
         }
+        UiNodeOp::Event { update } => {
+            if let Some(t) = &mut outgoing {
+                t.view.event(update);
+            }
+        }
+        UiNodeOp::Info { info } => {
+            if let Some(t) = &mut outgoing {
+                t.view.info(info);
+            }
+        }
+        UiNodeOp::Measure { wm, desired_size } => {
+            if let Some(t) = &mut outgoing {
+                *desired_size = c.measure(wm).max(t.view.measure(wm));
+            }
+        }
+        UiNodeOp::Layout { wl, final_size } => {
+            if let Some(t) = &mut outgoing {
+                // both views render in the child scope, so the child offset must not be given to only one of them.
+                wl.require_child_ref_frame();
+                *final_size = c.layout(wl).max(t.view.layout(wl));
+            }
+        }
+        UiNodeOp::Render { frame } => {
+            if let Some(t) = &mut outgoing {
+                let outgoing_opacity = opacity_key.bind_var_child(0, &progress, |p| 1.0 - p.0);
+                frame.push_opacity(outgoing_opacity, |frame| t.view.render(frame));
+                let opacity = opacity_key.bind_var_child(1, &progress, |p| p.0);
+                frame.push_opacity(opacity, |frame| c.render(frame));
+            }
+        }
+        UiNodeOp::RenderUpdate { update } => {
+            if let Some(t) = &mut outgoing {
+                update.update_f32_opt(opacity_key.update_var_child(0, &progress, |p| 1.0 - p.0));
+                t.view.render_update(update);
+                update.update_f32_opt(opacity_key.update_var_child(1, &progress, |p| p.0));
+                c.render_update(update);
+            }
+        }
         _ => {}
     })
 }
+
+/// Outgoing view during a [`view_transition`].
+///
+/// [`view_transition`]: fn@view_transition
+struct ViewTransition {
+    key: Option<TypeId>,
+    view: BoxedUiNode,
+    _animation: AnimationHandle,
+}
+
+/// Deinit and stash the outgoing view, stops the transition animation.
+fn end_transition(outgoing: &mut Option<ViewTransition>, pool: &Mutex<ViewPool>) {
+    if let Some(mut t) = outgoing.take() {
+        t.view.deinit();
+        pool.lock().stash(t.key, t.view);
+    }
+}
+
+/// Sets the [`wgt_fn!`] that is used to create a content for the error message when the view build panics.
+///
+/// When set the [`view`] property catches panics in the `update` handler and in the new view init, the panic is
+/// logged as an error and the view is replaced with the error view. When not set (the default) panics propagate.
+///
+/// A view that panics during init is deinited before it is dropped, so that the nodes that did init can release their
+/// resources, panics during this deinit are also caught. Note that a panic can leave the view in an inconsistent state,
+/// so the view nodes must not assume they are fully inited on deinit.
+///
+/// [`wgt_fn!`]: zng_wgt::wgt_fn
+/// [`view`]: fn@view
+#[property(CONTEXT, default(VIEW_ERROR_FN_VAR), widget_impl(DataView))]
+pub fn view_error_fn(child: impl UiNode, wgt_fn: impl IntoVar<WidgetFn<DataViewErrorArgs>>) -> impl UiNode {
+    with_context_var(child, VIEW_ERROR_FN_VAR, wgt_fn)
+}
+
+/// Sets the crossfade transition duration used when the [`view`] is replaced.
+///
+/// When set to a non-zero duration the outgoing view is retained and fades out while the incoming view fades in, both
+/// views update and layout during the transition, the widget desired size is the maximum of both views. The opacity
+/// animation uses render updates, a full render is only requested when the transition starts and ends. The outgoing view is deinited, or stashed if it is pooled, when the
+/// transition ends. If the view is replaced again during a transition the current transition ends immediately.
+///
+/// Note that there is no transition on init or from the nil view.
+///
+/// [`view`]: fn@view
+#[property(CONTEXT, default(VIEW_TRANSITION_VAR), widget_impl(DataView))]
+pub fn view_transition(child: impl UiNode, duration: impl IntoVar<Duration>) -> impl UiNode {
+    with_context_var(child, VIEW_TRANSITION_VAR, duration)
+}
+
+/// Calls `f`, catches the panic if [`VIEW_ERROR_FN_VAR`] is set.
+fn catch_view_panic(f: impl FnOnce()) -> Result<(), Txt> {
+    if VIEW_ERROR_FN_VAR.with(WidgetFn::is_nil) {
+        f();
+        return Ok(());
+    }
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).map_err(|p| {
+        let msg = if let Some(s) = p.downcast_ref::<&str>() {
+            Txt::from_str(s)
+        } else if let Some(s) = p.downcast_ref::<String>() {
+            Txt::from_str(s)
+        } else {
+            Txt::from_static("<unknown-panic-message-type>")
+        };
+        tracing::error!("panic in `DataView!` view build: {msg}");
+        msg
+    })
+}
+
+/// Init the new child, if it panics deinits it and replaces it with the error view.
+fn init_view(child: &mut BoxedUiNode, replace: &Mutex<Option<BoxedUiNode>>, pool: &Mutex<ViewPool>) {
+    if let Err(e) = catch_view_panic(|| child.init()) {
+        // deinit what did init, the error is already logged.
+        let _ = catch_view_panic(|| child.deinit());
+        replace_with_error(child, replace, pool, e);
+        child.init();
+    }
+}
+
+/// Replace the (deinited) child with the error view, drops any pending replacement.
+fn replace_with_error(child: &mut BoxedUiNode, replace: &Mutex<Option<BoxedUiNode>>, pool: &Mutex<ViewPool>, error: Txt) {
+    *replace.lock() = None;
+    let mut pool = pool.lock();
+    pool.next = None;
+    pool.current = None;
+    *child = VIEW_ERROR_FN_VAR.get().call(DataViewErrorArgs { error });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use zng_app::{
+        widget::{node::match_node_leaf, WidgetUpdateMode},
+        window::WINDOW,
+        DInstant, Deadline, APP,
+    };
+    use zng_var::{animation::AnimationTimer, VARS_APP};
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn view_transition_crossfade() {
+        let _app = APP.minimal().run_headless(false);
+        APP.start_manual_time();
+
+        let log = Arc::new(Mutex::new(vec![]));
+        let data = var(0u32);
+        let mut wgt = view_transition(
+            view(
+                NilUiNode,
+                data.clone(),
+                hn!(log, |a: &DataViewArgs<u32>| {
+                    if let Some(n) = a.get_new() {
+                        a.set_view(log_view(log.clone(), n));
+                    }
+                }),
+            ),
+            100.ms(),
+        );
+
+        WINDOW.with_test_context(WidgetUpdateMode::Bubble, || {
+            WINDOW.test_init(&mut wgt);
+            assert_eq!(take_log(&log), ["init 0"]);
+
+            // transition start, the old view is retained.
+            data.set(1u32);
+            test_update(&mut wgt);
+            assert_eq!(take_log(&log), ["init 1"]);
+            WINDOW.test_info(&mut wgt);
+            WINDOW.test_layout(&mut wgt, None);
+            WINDOW.test_render(&mut wgt);
+
+            advance(&mut wgt, 50.ms());
+            assert!(take_log(&log).is_empty());
+            WINDOW.test_render_update(&mut wgt);
+
+            // transition end.
+            advance(&mut wgt, 200.ms());
+            assert_eq!(take_log(&log), ["deinit 0"]);
+
+            // replacement mid-transition ends the current transition immediately.
+            data.set(2u32);
+            test_update(&mut wgt);
+            assert_eq!(take_log(&log), ["init 2"]);
+
+            advance(&mut wgt, 50.ms());
+            assert!(take_log(&log).is_empty());
+
+            data.set(3u32);
+            test_update(&mut wgt);
+            assert_eq!(take_log(&log), ["deinit 1", "init 3"]);
+
+            advance(&mut wgt, 200.ms());
+            assert_eq!(take_log(&log), ["deinit 2"]);
+
+            WINDOW.test_deinit(&mut wgt);
+            assert_eq!(take_log(&log), ["deinit 3"]);
+        });
+    }
+
+    #[test]
+    fn view_pooled() {
+        let _app = APP.minimal().run_headless(false);
+
+        let log = Arc::new(Mutex::new(vec![]));
+        let data = var(0u32);
+        let mut wgt = view(
+            NilUiNode,
+            data.clone(),
+            hn!(log, |a: &DataViewArgs<u32>| {
+                let log = log.clone();
+                // both closures return `BoxedUiNode`, as if built with `dyn_node`.
+                if a.data().get() % 2 == 0 {
+                    a.set_view_pooled(move || {
+                        log.lock().push("build 0".to_owned());
+                        log_view(log, 0).boxed()
+                    });
+                } else {
+                    a.set_view_pooled(move || {
+                        log.lock().push("build 1".to_owned());
+                        log_view(log, 1).boxed()
+                    });
+                }
+            }),
+        );
+
+        WINDOW.with_test_context(WidgetUpdateMode::Bubble, || {
+            WINDOW.test_init(&mut wgt);
+            assert_eq!(take_log(&log), ["build 0", "init 0"]);
+
+            data.set(1u32);
+            test_update(&mut wgt);
+            assert_eq!(take_log(&log), ["build 1", "deinit 0", "init 1"]);
+
+            // reused
+            data.set(2u32);
+            test_update(&mut wgt);
+            assert_eq!(take_log(&log), ["deinit 1", "init 0"]);
+
+            // retained
+            data.set(4u32);
+            test_update(&mut wgt);
+            assert!(take_log(&log).is_empty());
+
+            data.set(5u32);
+            test_update(&mut wgt);
+            assert_eq!(take_log(&log), ["deinit 0", "init 1"]);
+
+            WINDOW.test_deinit(&mut wgt);
+            assert_eq!(take_log(&log), ["deinit 1"]);
+        });
+    }
+
+    #[test]
+    fn view_error_update_panic() {
+        let _app = APP.minimal().run_headless(false);
+
+        let log = Arc::new(Mutex::new(vec![]));
+        let data = var(0u32);
+        let mut wgt = view_error_fn(
+            view(
+                NilUiNode,
+                data.clone(),
+                hn!(log, |a: &DataViewArgs<u32>| {
+                    if let Some(n) = a.get_new() {
+                        if n == 1 {
+                            panic!("test panic {n}");
+                        }
+                        a.set_view(log_view(log.clone(), n));
+                    }
+                }),
+            ),
+            error_view_fn(log.clone()),
+        );
+
+        WINDOW.with_test_context(WidgetUpdateMode::Bubble, || {
+            WINDOW.test_init(&mut wgt);
+            assert_eq!(take_log(&log), ["init 0"]);
+
+            data.set(1u32);
+            test_update(&mut wgt);
+            assert_eq!(take_log(&log), ["deinit 0", "init error test panic 1"]);
+
+            // recovers on the next data
+            data.set(2u32);
+            test_update(&mut wgt);
+            assert_eq!(take_log(&log), ["deinit error", "init 2"]);
+
+            WINDOW.test_deinit(&mut wgt);
+            assert_eq!(take_log(&log), ["deinit 2"]);
+        });
+    }
+
+    #[test]
+    fn view_error_init_panic() {
+        let _app = APP.minimal().run_headless(false);
+
+        let log = Arc::new(Mutex::new(vec![]));
+        let data = var(0u32);
+        let mut wgt = view_error_fn(
+            view(
+                NilUiNode,
+                data.clone(),
+                hn!(log, |a: &DataViewArgs<u32>| {
+                    if let Some(n) = a.get_new() {
+                        let view = log_view(log.clone(), n);
+                        if n == 1 {
+                            // the child inits, then panics.
+                            a.set_view(match_node(view, |c, op| {
+                                if let UiNodeOp::Init = op {
+                                    c.init();
+                                    panic!("test panic");
+                                }
+                            }));
+                        } else {
+                            a.set_view(view);
+                        }
+                    }
+                }),
+            ),
+            error_view_fn(log.clone()),
+        );
+
+        WINDOW.with_test_context(WidgetUpdateMode::Bubble, || {
+            WINDOW.test_init(&mut wgt);
+            assert_eq!(take_log(&log), ["init 0"]);
+
+            // partially inited view is deinited
+            data.set(1u32);
+            test_update(&mut wgt);
+            assert_eq!(take_log(&log), ["deinit 0", "init 1", "deinit 1", "init error test panic"]);
+
+            WINDOW.test_deinit(&mut wgt);
+            assert_eq!(take_log(&log), ["deinit error"]);
+        });
+    }
+
+    #[test]
+    fn view_pooled_transition_toggle() {
+        let _app = APP.minimal().run_headless(false);
+        APP.start_manual_time();
+
+        let log = Arc::new(Mutex::new(vec![]));
+        let alive = Arc::new(AtomicUsize::new(0));
+        let data = var(0u32);
+        let mut wgt = view_transition(
+            view(
+                NilUiNode,
+                data.clone(),
+                hn!(log, alive, |a: &DataViewArgs<u32>| {
+                    let log = log.clone();
+                    let alive = alive.clone();
+                    if a.data().get() % 2 == 0 {
+                        a.set_view_pooled(move || alive_view(alive, log_view(log, 0)));
+                    } else {
+                        a.set_view_pooled(move || alive_view(alive, log_view(log, 1)));
+                    }
+                }),
+            ),
+            100.ms(),
+        );
+
+        WINDOW.with_test_context(WidgetUpdateMode::Bubble, || {
+            WINDOW.test_init(&mut wgt);
+
+            // toggle faster than the transition, the outgoing view is not reused, a new view is built.
+            for n in 1..=10u32 {
+                data.set(n);
+                test_update(&mut wgt);
+                advance(&mut wgt, 10.ms());
+            }
+            // current, outgoing and at most one stashed view per build closure.
+            assert!(alive.load(Ordering::Relaxed) <= 4, "{} views alive", alive.load(Ordering::Relaxed));
+
+            advance(&mut wgt, 200.ms());
+            assert!(alive.load(Ordering::Relaxed) <= 3, "{} views alive", alive.load(Ordering::Relaxed));
+            take_log(&log);
+
+            // reuses stashed views after the transition.
+            let alive_before = alive.load(Ordering::Relaxed);
+            data.set(11u32);
+            test_update(&mut wgt);
+            advance(&mut wgt, 50.ms());
+            advance(&mut wgt, 200.ms());
+            assert_eq!(take_log(&log), ["init 1", "deinit 0"]);
+            // no new view built
+            assert!(alive.load(Ordering::Relaxed) <= alive_before);
+
+            WINDOW.test_deinit(&mut wgt);
+        });
+        assert_eq!(alive.load(Ordering::Relaxed), 0);
+    }
+
+    /// Counts the `child` instances alive.
+    fn alive_view(alive: Arc<AtomicUsize>, child: impl UiNode) -> impl UiNode {
+        struct Alive(Arc<AtomicUsize>);
+        impl Drop for Alive {
+            fn drop(&mut self) {
+                self.0.fetch_sub(1, Ordering::Relaxed);
+            }
+        }
+        alive.fetch_add(1, Ordering::Relaxed);
+        let alive = Alive(alive);
+        match_node(child, move |_, _| {
+            let _ = &alive;
+        })
+    }
+
+    fn error_view_fn(log: Arc<Mutex<Vec<String>>>) -> WidgetFn<DataViewErrorArgs> {
+        WidgetFn::new(move |args: DataViewErrorArgs| {
+            let log = log.clone();
+            match_node_leaf(move |op| match op {
+                UiNodeOp::Init => log.lock().push(format!("init error {}", args.error)),
+                UiNodeOp::Deinit => log.lock().push("deinit error".to_owned()),
+                _ => {}
+            })
+        })
+    }
+
+    fn log_view(log: Arc<Mutex<Vec<String>>>, n: u32) -> impl UiNode {
+        match_node_leaf(move |op| match op {
+            UiNodeOp::Init => log.lock().push(format!("init {n}")),
+            UiNodeOp::Deinit => log.lock().push(format!("deinit {n}")),
+            _ => {}
+        })
+    }
+
+    fn take_log(log: &Mutex<Vec<String>>) -> Vec<String> {
+        std::mem::take(&mut *log.lock())
+    }
+
+    /// Apply var updates and update.
+    fn test_update(wgt: &mut impl UiNode) {
+        WINDOW.test_update(wgt, None);
+        WINDOW.test_update(wgt, None);
+    }
+
+    /// Advance the manual time and animations.
+    fn advance(wgt: &mut impl UiNode, time: Duration) {
+        APP.advance_manual_time(time);
+        VARS_APP.update_animations(&mut TestTimer);
+        test_update(wgt);
+    }
+
+    struct TestTimer;
+    impl AnimationTimer for TestTimer {
+        fn elapsed(&mut self, deadline: Deadline) -> bool {
+            deadline.has_elapsed()
+        }
+
+        fn register(&mut self, _: Deadline) {}
+
+        fn now(&self) -> DInstant {
+            INSTANT.now()
+        }
+    }
+}
@@ -136,6 +136,11 @@ context_var! {
     /// Is `Align::CENTER` by default.
     pub static IMAGE_ALIGN_VAR: Align = Align::CENTER;
 
+    /// Align of the image visible region when it is larger than the image widget final size.
+    ///
+    /// Is the [`IMAGE_ALIGN_VAR`] by default.
+    pub static IMAGE_CROP_ANCHOR_VAR: Align = IMAGE_ALIGN_VAR;
+
     /// Offset applied to the image after all measure and arrange.
     pub static IMAGE_OFFSET_VAR: Vector = Vector::default();
 
@@ -153,6 +158,21 @@ context_var! {
     ///
     /// is `Size::zero` by default.
     pub static IMAGE_REPEAT_SPACING_VAR: Size = Size::zero();
+
+    /// If animated images are playing.
+    ///
+    /// Is `true` by default.
+    pub static IMAGE_PLAYING_VAR: bool = true;
+
+    /// If animated images restart after the last frame.
+    ///
+    /// Is `true` by default.
+    pub static IMAGE_LOOP_VAR: bool = true;
+
+    /// Frame index shown when animated images are not playing.
+    ///
+    /// Is `0` by default.
+    pub static IMAGE_FRAME_VAR: usize = 0;
 }
 
 /// Sets the [`ImageFit`] of all inner images.
@@ -228,6 +248,24 @@ pub fn img_align(child: impl UiNode, fit: impl IntoVar<Align>) -> impl UiNode {
     with_context_var(child, IMAGE_ALIGN_VAR, fit)
 }
 
+/// Sets the [`Align`] of the visible region of all inner images that are larger than the image widget area.
+///
+/// When the fitted image overflows the widget area, as is common with [`ImageFit::Cover`], this alignment selects
+/// what part of the image is visible, for example, [`TOP`] keeps the top of portraits visible. Images smaller than the
+/// widget area are still aligned by [`img_align`].
+///
+/// By default this is the same as [`img_align`].
+///
+/// This property sets the [`IMAGE_CROP_ANCHOR_VAR`].
+///
+/// [`TOP`]: zng_wgt::prelude::Align::TOP
+/// [`Align`]: zng_wgt::prelude::Align
+/// [`img_align`]: fn@img_align
+#[property(CONTEXT, default(IMAGE_CROP_ANCHOR_VAR), widget_impl(Image))]
+pub fn img_crop_anchor(child: impl UiNode, anchor: impl IntoVar<Align>) -> impl UiNode {
+    with_context_var(child, IMAGE_CROP_ANCHOR_VAR, anchor)
+}
+
 /// Sets a [`Point`] that is an offset applied to all inner images within each image widget area.
 ///
 /// Relative values are calculated from the widget final size. Note that this is different the applying the
@@ -325,6 +363,42 @@ pub fn img_cache(child: impl UiNode, enabled: impl IntoVar<bool>) -> impl UiNode
     with_context_var(child, IMAGE_CACHE_VAR, enabled)
 }
 
+/// Sets if animated images are playing.
+///
+/// Animated GIF, APNG and WebP images play by default, when set to `false` the [`img_frame`] is shown.
+/// Still images are not affected by this property.
+///
+/// This property sets the [`IMAGE_PLAYING_VAR`].
+///
+/// [`img_frame`]: fn@img_frame
+#[property(CONTEXT, default(IMAGE_PLAYING_VAR), widget_impl(Image))]
+pub fn img_playing(child: impl UiNode, playing: impl IntoVar<bool>) -> impl UiNode {
+    with_context_var(child, IMAGE_PLAYING_VAR, playing)
+}
+
+/// Sets if animated images restart after the last frame.
+///
+/// If `false` the animation stops on the last frame.
+///
+/// This property sets the [`IMAGE_LOOP_VAR`].
+#[property(CONTEXT, default(IMAGE_LOOP_VAR), widget_impl(Image))]
+pub fn img_loop(child: impl UiNode, enabled: impl IntoVar<bool>) -> impl UiNode {
+    with_context_var(child, IMAGE_LOOP_VAR, enabled)
+}
+
+/// Sets the frame of animated images.
+///
+/// When [`img_playing`] is `true` the animation starts from this frame, when it is `false` this frame is shown.
+/// Indexes out of bounds clamp to the last frame.
+///
+/// This property sets the [`IMAGE_FRAME_VAR`].
+///
+/// [`img_playing`]: fn@img_playing
+#[property(CONTEXT, default(IMAGE_FRAME_VAR), widget_impl(Image))]
+pub fn img_frame(child: impl UiNode, index: impl IntoVar<usize>) -> impl UiNode {
+    with_context_var(child, IMAGE_FRAME_VAR, index)
+}
+
 /// Sets custom image load and decode limits.
 ///
 /// If not set or set to `None` the [`IMAGES.limits`] is used.
@@ -23,7 +23,7 @@ pub mod node;
 
 /// Image presenter.
 ///
-/// This widget loads a still image from a variety of sources and presents it.
+/// This widget loads a still or animated image from a variety of sources and presents it.
 ///
 #[widget($crate::Image {
     ($source:expr) => {
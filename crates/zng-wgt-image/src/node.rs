@@ -1,14 +1,14 @@
 //! UI nodes used for building the image widget.
 
-use std::mem;
+use std::{mem, time::Duration};
 
 use zng_ext_image::{ImageCacheMode, ImagePpi, ImageRenderArgs, IMAGES};
 use zng_wgt_stack::stack_nodes_layout_by;
 
 use super::image_properties::{
-    ImageFit, ImgErrorArgs, ImgLoadingArgs, IMAGE_ALIGN_VAR, IMAGE_CACHE_VAR, IMAGE_CROP_VAR, IMAGE_DOWNSCALE_VAR, IMAGE_ERROR_FN_VAR,
-    IMAGE_FIT_VAR, IMAGE_LIMITS_VAR, IMAGE_LOADING_FN_VAR, IMAGE_OFFSET_VAR, IMAGE_RENDERING_VAR, IMAGE_SCALE_FACTOR_VAR,
-    IMAGE_SCALE_PPI_VAR, IMAGE_SCALE_VAR,
+    ImageFit, ImgErrorArgs, ImgLoadingArgs, IMAGE_ALIGN_VAR, IMAGE_CACHE_VAR, IMAGE_CROP_ANCHOR_VAR, IMAGE_CROP_VAR, IMAGE_DOWNSCALE_VAR,
+    IMAGE_ERROR_FN_VAR, IMAGE_FIT_VAR, IMAGE_FRAME_VAR, IMAGE_LIMITS_VAR, IMAGE_LOADING_FN_VAR, IMAGE_LOOP_VAR, IMAGE_OFFSET_VAR,
+    IMAGE_PLAYING_VAR, IMAGE_RENDERING_VAR, IMAGE_SCALE_FACTOR_VAR, IMAGE_SCALE_PPI_VAR, IMAGE_SCALE_VAR,
 };
 
 use super::*;
@@ -191,10 +191,16 @@ pub fn image_loading_presenter(child: impl UiNode) -> impl UiNode {
 /// * [`IMAGE_SCALE_VAR`]: Custom scale applied to the desired size.
 /// * [`IMAGE_FIT_VAR`]: Defines the image final size.
 /// * [`IMAGE_ALIGN_VAR`]: Defines the image alignment in the presenter final size.
+/// * [`IMAGE_CROP_ANCHOR_VAR`]: Defines the image alignment when it is larger than the presenter final size.
 /// * [`IMAGE_RENDERING_VAR`]: Defines the image resize algorithm used in the GPU.
 /// * [`IMAGE_OFFSET_VAR`]: Defines an offset applied to the image after all measure and arrange.
+/// * [`IMAGE_PLAYING_VAR`]: Defines if animated images are playing.
+/// * [`IMAGE_LOOP_VAR`]: Defines if animated images restart after the last frame.
+/// * [`IMAGE_FRAME_VAR`]: Defines the animated image frame shown when not playing.
 pub fn image_presenter() -> impl UiNode {
     let mut img_size = PxSize::zero();
+    let mut frame_index = 0;
+    let mut frame_timer = None;
     let mut render_clip = PxRect::zero();
     let mut render_img_size = PxSize::zero();
     let mut render_tile_size = PxSize::zero();
@@ -212,15 +218,29 @@ pub fn image_presenter() -> impl UiNode {
                 .sub_var_layout(&IMAGE_SCALE_VAR)
                 .sub_var_layout(&IMAGE_FIT_VAR)
                 .sub_var_layout(&IMAGE_ALIGN_VAR)
+                .sub_var_layout(&IMAGE_CROP_ANCHOR_VAR)
                 .sub_var_layout(&IMAGE_OFFSET_VAR)
                 .sub_var_layout(&IMAGE_REPEAT_VAR)
                 .sub_var_layout(&IMAGE_REPEAT_SPACING_VAR)
-                .sub_var_render(&IMAGE_RENDERING_VAR);
+                .sub_var_render(&IMAGE_RENDERING_VAR)
+                .sub_var(&IMAGE_PLAYING_VAR)
+                .sub_var(&IMAGE_LOOP_VAR)
+                .sub_var(&IMAGE_FRAME_VAR);
 
-            img_size = CONTEXT_IMAGE_VAR.with(Img::size);
+            CONTEXT_IMAGE_VAR.with(|img| {
+                img_size = img.size();
+                frame_index = IMAGE_FRAME_VAR.get().min(img.frame_count() - 1);
+                frame_timer = next_frame_timer(img, frame_index);
+            });
+        }
+        UiNodeOp::Deinit => {
+            frame_timer = None;
         }
         UiNodeOp::Update { .. } => {
             if let Some(img) = CONTEXT_IMAGE_VAR.get_new() {
+                frame_index = IMAGE_FRAME_VAR.get().min(img.frame_count() - 1);
+                frame_timer = next_frame_timer(&img, frame_index);
+
                 let ig_size = img.size();
                 if img_size != ig_size {
                     img_size = ig_size;
@@ -228,6 +248,21 @@ pub fn image_presenter() -> impl UiNode {
                 } else if img.is_loaded() {
                     WIDGET.render();
                 }
+            } else if IMAGE_FRAME_VAR.is_new() || IMAGE_PLAYING_VAR.is_new() || IMAGE_LOOP_VAR.is_new() {
+                CONTEXT_IMAGE_VAR.with(|img| {
+                    if IMAGE_FRAME_VAR.is_new() || (IMAGE_PLAYING_VAR.is_new() && !IMAGE_PLAYING_VAR.get()) {
+                        frame_index = IMAGE_FRAME_VAR.get().min(img.frame_count() - 1);
+                    }
+                    frame_timer = next_frame_timer(img, frame_index);
+                });
+                WIDGET.render();
+            } else if frame_timer.as_ref().map(|(t, _)| t.is_new()).unwrap_or(false) {
+                CONTEXT_IMAGE_VAR.with(|img| {
+                    let count = img.frame_count();
+                    frame_index = if frame_index + 1 < count { frame_index + 1 } else { 0 };
+                    frame_timer = next_frame_timer(img, frame_index);
+                });
+                WIDGET.render();
             }
         }
         UiNodeOp::Measure { desired_size, .. } => {
@@ -291,6 +326,7 @@ pub fn image_presenter() -> impl UiNode {
             // - Fit the cropped and scaled image to the constraints, add a bounds clip to the crop clip.
 
             let mut align = IMAGE_ALIGN_VAR.get();
+            let anchor = IMAGE_CROP_ANCHOR_VAR.get();
 
             let min_size = metrics.constraints().clamp_size(r_clip.size);
             let wgt_ratio = metrics.constraints().with_min_size(min_size).fill_ratio(r_clip.size);
@@ -336,7 +372,12 @@ pub fn image_presenter() -> impl UiNode {
                 r_offset.x = -r_clip.origin.x;
             } else {
                 let diff = wgt_size.width - r_clip.size.width;
-                let offset = diff * align.x(metrics.direction());
+                let x = if diff < Px(0) {
+                    anchor.x(metrics.direction())
+                } else {
+                    align.x(metrics.direction())
+                };
+                let offset = diff * x;
                 r_offset.x += offset;
                 if diff < Px(0) {
                     r_clip.origin.x -= offset;
@@ -351,7 +392,8 @@ pub fn image_presenter() -> impl UiNode {
                 r_offset.y = -r_clip.origin.y;
             } else {
                 let diff = wgt_size.height - r_clip.size.height;
-                let offset = diff * align.y();
+                let y = if diff < Px(0) { anchor.y() } else { align.y() };
+                let offset = diff * y;
                 r_offset.y += offset;
                 if diff < Px(0) {
                     r_clip.origin.y -= offset;
@@ -402,6 +444,8 @@ pub fn image_presenter() -> impl UiNode {
         }
         UiNodeOp::Render { frame } => {
             CONTEXT_IMAGE_VAR.with(|img| {
+                let frame_img = if frame_index > 0 { img.frame(frame_index) } else { None };
+                let img = frame_img.as_ref().unwrap_or(img);
                 if img.is_loaded() && !img_size.is_empty() && !render_clip.is_empty() {
                     if render_offset != PxVector::zero() {
                         let transform = PxTransform::from(render_offset);
@@ -432,6 +476,25 @@ pub fn image_presenter() -> impl UiNode {
     })
 }
 
+/// Starts the timer that advances from the animation frame `index`, if the image is animated and playing.
+fn next_frame_timer(img: &Img, index: usize) -> Option<(DeadlineVar, VarHandle)> {
+    if !img.is_animated() || !IMAGE_PLAYING_VAR.get() {
+        return None;
+    }
+    if index + 1 >= img.frame_count() && !IMAGE_LOOP_VAR.get() {
+        return None;
+    }
+
+    let mut delay = img.frame(index)?.frame_delay();
+    if delay <= Duration::from_millis(10) {
+        // same as browsers, zero or very small delays are presented at 10fps
+        delay = Duration::from_millis(100);
+    }
+    let timer = TIMERS.deadline(delay);
+    let handle = timer.subscribe(UpdateOp::Update, WIDGET.id());
+    Some((timer, handle))
+}
+
 fn tile_leftover(tile_size: PxSize, wgt_size: PxSize) -> PxSize {
     if tile_size.is_empty() || wgt_size.is_empty() {
         return PxSize::zero();
@@ -32,7 +32,7 @@ pub struct MaterialIconsManager;
 impl MaterialIconsManager {
     #[cfg(feature = "embedded")]
     fn register_fonts(&self) {
-        let sets = [
+        let sets: &[(FontName, &'static [u8])] = &[
             #[cfg(feature = "outlined")]
             (outlined::FONT_NAME, outlined::FONT_BYTES),
             #[cfg(feature = "filled")]
@@ -44,7 +44,7 @@ impl MaterialIconsManager {
         ];
 
         for (name, bytes) in sets {
-            let font = zng_ext_font::CustomFont::from_bytes(name, zng_ext_font::FontDataRef::from_static(bytes), 0);
+            let font = zng_ext_font::CustomFont::from_bytes(name.clone(), zng_ext_font::FontDataRef::from_static(*bytes), 0);
             zng_ext_font::FONTS.register(font);
         }
     }
@@ -66,6 +66,8 @@ impl zng_app::AppExtension for MaterialIconsManager {
                     ("rounded/", rounded::get),
                     #[cfg(feature = "sharp")]
                     ("sharp/", sharp::get),
+                    #[cfg(feature = "variable")]
+                    ("variable/", variable::get),
                 ];
                 for (name, get) in sets {
                     if let Some(key) = strong_key.strip_prefix(name) {
@@ -80,7 +82,7 @@ impl zng_app::AppExtension for MaterialIconsManager {
         }));
 
         ICONS.register_fallback(wgt_fn!(|args: IconRequestArgs| {
-            let sets = [
+            let sets: &[fn(&str) -> Option<GlyphIcon>] = &[
                 #[cfg(feature = "outlined")]
                 outlined::get,
                 #[cfg(feature = "filled")]
@@ -249,3 +251,74 @@ pub mod sharp {
     include!(concat!(env!("OUT_DIR"), "/generated.sharp.map.rs"));
     getters!(FONT_NAME, MAP);
 }
+
+/// Variable icons.
+///
+/// This is the "Material Symbols Outlined" variable font, it has the `FILL`, `wght`, `GRAD` and `opsz` axes that can be
+/// set at runtime, so the icon fill and weight can be animated without swapping fonts.
+///
+/// The font file is not embedded, download the `MaterialSymbolsOutlined[FILL,GRAD,opsz,wght].ttf` font from the
+/// [Material Design Icons] project and register it using [`variable::register`] or install it in the system.
+///
+/// # Icons
+///
+/// Use the [`ICONS`] service with key `"material/variable/{name}"` to get an widget that renders the icon.
+///
+/// Use [`variable::req`] to get a [`GlyphIcon`] directly for use in the [`Icon!`] widget and
+/// [`MaterialIconExt::with_variation`] to set the fill and weight.
+///
+/// The icons are resolved by name using the font ligatures, so the names are not validated before the font is loaded.
+///
+/// [`Icon!`]: struct@Icon
+/// [`MaterialIconExt::with_variation`]: variable::MaterialIconExt::with_variation
+/// [Material Design Icons]: https://github.com/google/material-design-icons
+#[cfg(feature = "variable")]
+pub mod variable {
+    use super::*;
+
+    /// "Material Symbols Outlined".
+    pub const FONT_NAME: FontName = FontName::from_static("Material Symbols Outlined");
+
+    /// Register the variable font from the font file data.
+    pub fn register(data: zng_ext_font::FontDataRef) {
+        let font = zng_ext_font::CustomFont::from_bytes(FONT_NAME, data, 0);
+        zng_ext_font::FONTS.register(font);
+    }
+
+    /// Gets the [`GlyphIcon`].
+    ///
+    /// Returns `None` if the `key` is not a valid icon name, note that the name is only matched with an
+    /// icon when the font is loaded.
+    pub fn get(key: &str) -> Option<GlyphIcon> {
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_') {
+            return None;
+        }
+        Some(GlyphIcon::new(FONT_NAME, zng_wgt::prelude::Txt::from_str(key)).with_ligatures())
+    }
+
+    /// Require the [`GlyphIcon`], logs an error if not valid.
+    pub fn req(key: &str) -> GlyphIcon {
+        match get(key) {
+            Some(g) => g,
+            None => {
+                tracing::error!("icon {key:?} not valid in `variable`");
+                GlyphIcon::new("", '\0')
+            }
+        }
+    }
+
+    /// Extension methods for [`GlyphIcon`] from the variable font.
+    pub trait MaterialIconExt {
+        /// Set the `FILL` and `wght` axis values.
+        ///
+        /// The `fill` is in the `0.0..=1.0` range, the `weight` is in the `100.0..=700.0` range.
+        fn with_variation(self, fill: f32, weight: f32) -> GlyphIcon;
+    }
+    impl MaterialIconExt for GlyphIcon {
+        fn with_variation(mut self, fill: f32, weight: f32) -> GlyphIcon {
+            self.variations.insert(b"FILL".into(), fill);
+            self.variations.insert(b"wght".into(), weight);
+            self
+        }
+    }
+}
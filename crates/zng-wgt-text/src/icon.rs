@@ -1,6 +1,9 @@
 //! Glyph icon widget, properties and nodes.
 
-use zng_ext_font::{font_features::FontFeatures, FontName, FontSize};
+use zng_ext_font::{
+    font_features::{FontFeatures, FontVariations},
+    FontName, FontSize,
+};
 use zng_wgt::prelude::*;
 
 use std::fmt;
@@ -43,6 +46,7 @@ impl Icon {
                 let node = crate::font_family(node, icon.map(|i| i.font.clone().into()));
                 let node = icon_size(node);
                 let node = crate::font_features(node, icon.map_ref(|i| &i.features));
+                let node = crate::font_variations(node, icon.map_ref(|i| &i.variations));
                 crate::font_color(node, ICON_COLOR_VAR)
             });
         });
@@ -105,6 +109,8 @@ pub struct GlyphIcon {
     pub font: FontName,
     /// Font features, like ligatures.
     pub features: FontFeatures,
+    /// Font variations, for icon fonts that are variable fonts.
+    pub variations: FontVariations,
     /// Icon glyph.
     pub glyph: GlyphSource,
 }
@@ -114,6 +120,7 @@ impl GlyphIcon {
         GlyphIcon {
             font: font.into(),
             features: FontFeatures::new(),
+            variations: FontVariations::new(),
             glyph: glyph.into(),
         }
     }
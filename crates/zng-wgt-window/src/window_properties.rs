@@ -2,8 +2,8 @@ use std::time::Duration;
 
 use zng_ext_config::{AnyConfig as _, ConfigKey, ConfigStatus, ConfigValue, CONFIG};
 use zng_ext_window::{
-    AutoSize, FrameCaptureMode, MonitorQuery, WINDOW_Ext as _, WindowButton, WindowIcon, WindowLoadingHandle, WindowState, WindowVars,
-    MONITORS, WINDOW_LOAD_EVENT,
+    AutoSize, FrameCaptureMode, MonitorQuery, WINDOW_Ext as _, WindowButton, WindowIcon, WindowLevel, WindowLoadingHandle, WindowState,
+    WindowVars, MONITORS, WINDOW_LOAD_EVENT,
 };
 use zng_wgt::prelude::*;
 
@@ -80,8 +80,11 @@ set_properties! {
 
     resizable: bool,
     movable: bool,
+    clamp_to_monitor: bool,
+    aspect_ratio: Option<f32>,
 
     always_on_top: bool,
+    window_level: WindowLevel,
 
     visible: bool,
     taskbar_visible: bool,
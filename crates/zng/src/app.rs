@@ -278,7 +278,8 @@
 //!
 //! The main loop coordinates view-process events, timers, app events and updates. There is no scheduler, update and event requests
 //! are captured and coalesced to various buffers that are drained in known sequential order. App extensions update one at a time
-//! in the order they are registered. Windows and widgets update in parallel by default, this is controlled by [`WINDOWS.parallel`] and [`parallel`].
+//! in the order they are registered, extensions can declare order constraints using [`AppExtensionsInfo::before`] and
+//! [`AppExtensionsInfo::after`]. Windows and widgets update in parallel by default, this is controlled by [`WINDOWS.parallel`] and [`parallel`].
 //!
 //! 1. Sleep if there are not pending events or updates.
 //!    * If the view-process is busy blocks until it sends a message, this is a mechanism to stop the app-process
@@ -299,7 +300,8 @@
 //!
 //! #### View Events Loop
 //!
-//! All pending events send by the view-process are coalesced and notify sequentially.
+//! All pending events send by the view-process are coalesced and notify sequentially. The view-process init
+//! event notifies immediately, when it is the first init [`AppExtension::view_process_inited`] is called before notifying.
 //!
 //! 1. For each event in the received order (FIFO) that converts to a RAW event.
 //!     1. Calls [`AppExtension::event_preview`].
@@ -308,7 +310,8 @@
 //!         * Raw events don't target any widget, but widgets can subscribe, subscribers receive the event in parallel by default.
 //!     4. Calls [`AppExtension::event`].
 //!     5. Calls [`Event::on_event`] handlers.
-//!     6. Does an [updates loop](#updates-loop).
+//!     6. Calls [`AppExtension::on_unhandled_event`] if propagation was not stopped.
+//!     7. Does an [updates loop](#updates-loop).
 //! 2. Frame rendered raw event.
 //!     * Same notification sequence as other view-events, just delayed.
 //!
@@ -359,7 +362,8 @@
 //!         * If the event targets multiple widgets they receive it in parallel by default.
 //!     4. Calls [`AppExtension::event`].
 //!     5. Calls [`Event::on_event`] handlers.
-//!     6. Does an [updates loop](#updates-loop).
+//!     6. Calls [`AppExtension::on_unhandled_event`] if propagation was not stopped.
+//!     7. Does an [updates loop](#updates-loop).
 //!
 //! #### Layout Loop and Render
 //!
@@ -401,8 +405,8 @@
 
 pub use zng_app::{
     on_app_start, print_tracing, print_tracing_filter, AppControlFlow, AppEventObserver, AppExtended, AppExtension, AppExtensionBoxed,
-    AppExtensionInfo, AppStartArgs, DInstant, Deadline, ExitRequestedArgs, HeadlessApp, InstantMode, EXIT_CMD, EXIT_REQUESTED_EVENT,
-    INSTANT,
+    AppExtensionInfo, AppExtensionsInfo, AppStartArgs, DInstant, Deadline, ExitRequestedArgs, HeadlessApp, InstantMode, EXIT_CMD,
+    EXIT_REQUESTED_EVENT, INSTANT,
 };
 
 #[cfg(feature = "test_util")]
@@ -51,4 +51,6 @@
 //!
 //! See [`zng_wgt_data_view`] for the full view API.
 
-pub use zng_wgt_data_view::{DataView, DataViewArgs};
+pub use zng_wgt_data_view::{
+    view_error_fn, view_transition, DataView, DataViewArgs, DataViewErrorArgs, VIEW_ERROR_FN_VAR, VIEW_TRANSITION_VAR,
+};
@@ -116,6 +116,15 @@
 //! Hot node reinit reloads the entire tree branch, so descendants of hot nodes are reinited too. This may cause some state to be lost,
 //! in particular all state inited inside the hot node will be reinited.
 //!
+//! ##### Static State
+//!
+//! Statics declared using [`app_local!`], [`lazy_static!`] and [`hot_static!`] in the library crate are patched on reload
+//! to reference the same statics in the binary crate, so their state is already kept across reloads, no serialization is needed.
+//! Because of this you cannot change the type of these statics, doing so causes undefined behavior as the new code will
+//! reference a value of the old type. Statics added after the app started are not patched and are reinited every reload.
+//!
+//! [`app_local!`]: crate::app::app_local
+//!
 //! ##### Hot Libraries Don't Unload
 //!
 //! Every hot reload represents a new version of the library reloading and the previous one cannot be unloaded because static references
@@ -159,6 +168,6 @@ pub use zng_ext_hot_reload::hot_node;
 pub use zng_ext_hot_reload::zng_hot_entry;
 
 #[cfg(feature = "hot_reload")]
-pub use zng_ext_hot_reload::{BuildArgs, BuildError, HOT_RELOAD};
+pub use zng_ext_hot_reload::{BuildArgs, BuildError, HotBuildStatus, HotStatus, HOT_RELOAD};
 
 pub use zng_unique_id::{hot_static, hot_static_ref, lazy_static, lazy_static_init};
@@ -95,6 +95,9 @@ pub use zng_wgt_text::icon::{ico_color, ico_size, GlyphIcon, GlyphSource, Icon};
 /// You can disambiguate icons by using a the `"material/{set}/{name}"` where `{set}` is one of the items from the list above,
 /// and `{name}` is the icon name.
 ///
+/// The `"material_icons_variable"` feature enables the variable icon set, its icons are only available using the
+/// `"material/variable/{name}"` key and the font must be registered by the app.
+///
 /// # Full API
 ///
 /// See [`zng_wgt_material_icons`] for the full API.
@@ -107,4 +110,6 @@ pub mod material {
     pub use zng_wgt_material_icons::rounded;
     #[cfg(feature = "material_icons_sharp")]
     pub use zng_wgt_material_icons::sharp;
+    #[cfg(feature = "material_icons_variable")]
+    pub use zng_wgt_material_icons::variable;
 }
@@ -614,7 +614,7 @@ mod __prelude {
         focus::{cmd::CommandFocusExt as _, iter::IterFocusableExt as _, WidgetInfoFocusExt as _, FOCUS},
         gesture::{CommandShortcutMatchesExt as _, HeadlessAppGestureExt as _},
         keyboard::HeadlessAppKeyboardExt as _,
-        mouse::WidgetInfoMouseExt as _,
+        mouse::{HeadlessAppMouseExt as _, WidgetInfoMouseExt as _},
     };
 
     pub use zng_ext_l10n::{l10n, lang, L10N};
@@ -890,6 +890,7 @@ mod defaults {
                 feature = "material_icons_filled",
                 feature = "material_icons_rounded",
                 feature = "material_icons_sharp",
+                feature = "material_icons_variable",
             ))]
             let r = r.extend(zng_wgt_material_icons::MaterialIconsManager);
 
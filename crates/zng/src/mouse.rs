@@ -37,9 +37,9 @@
 //! See [`zng_ext_input::mouse`] and [`zng_wgt_input::mouse`] for the full mouse API.
 
 pub use zng_ext_input::mouse::{
-    ButtonRepeatConfig, ButtonState, ClickMode, ClickTrigger, MouseButton, MouseClickArgs, MouseHoverArgs, MouseInputArgs, MouseMoveArgs,
-    MousePosition, MouseScrollDelta, MouseWheelArgs, MultiClickConfig, WidgetInfoBuilderMouseExt, WidgetInfoMouseExt, MOUSE,
-    MOUSE_CLICK_EVENT, MOUSE_HOVERED_EVENT, MOUSE_INPUT_EVENT, MOUSE_MOVE_EVENT, MOUSE_WHEEL_EVENT,
+    ButtonRepeatConfig, ButtonState, ClickMode, ClickTrigger, HeadlessAppMouseExt, MouseButton, MouseClickArgs, MouseHoverArgs,
+    MouseInputArgs, MouseMoveArgs, MousePosition, MouseScrollDelta, MouseWheelArgs, MultiClickConfig, WidgetInfoBuilderMouseExt,
+    WidgetInfoMouseExt, MOUSE, MOUSE_CLICK_EVENT, MOUSE_HOVERED_EVENT, MOUSE_INPUT_EVENT, MOUSE_MOVE_EVENT, MOUSE_WHEEL_EVENT,
 };
 
 pub use zng_wgt_input::mouse::{